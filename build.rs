@@ -17,6 +17,27 @@ fn main() {
             "{} v{} {} is {} > {} (this rustc)",
             PACKAGE, VERSION, M_V, join(&msrv), join(&rustv));
     }
+
+    // The kv feature requires log 0.4.21+, which requires rustc 1.60+
+    let kv_msrv = vec![1, 60];
+    if env::var_os("CARGO_FEATURE_KV").is_some() && rustv < kv_msrv {
+        panic!(
+            "{} v{} kv feature {} is {} > {} (this rustc)",
+            PACKAGE, VERSION, M_V, join(&kv_msrv), join(&rustv));
+    }
+
+    // #[track_caller], for the extension traits, and
+    // std::backtrace::Backtrace, for the panic hook
+    if rustv >= vec![1, 80] {
+        println!("cargo:rustc-check-cfg=cfg(tao_log_track_caller)");
+        println!("cargo:rustc-check-cfg=cfg(tao_log_backtrace)");
+    }
+    if rustv >= vec![1, 46] {
+        println!("cargo:rustc-cfg=tao_log_track_caller");
+    }
+    if rustv >= vec![1, 65] {
+        println!("cargo:rustc-cfg=tao_log_backtrace");
+    }
 }
 
 fn join(ver: &Vec<u16>) -> String {
@@ -0,0 +1,137 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Support for logging errors: failures of `Result` or `Option` values and
+//! error source chains.
+
+#[cfg(tao_log_backtrace)] use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+
+/// `Display` of the `source()` chain of an error, each cause preceded by
+/// `": "`, e.g. `": inner: root"`, or nothing if there is no source.
+#[doc(hidden)]
+pub struct Sources<'a>(pub Option<&'a dyn Error>);
+
+impl<'a> fmt::Display for Sources<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut next = self.0;
+        while let Some(e) = next {
+            write!(f, ": {}", e)?;
+            next = e.source();
+        }
+        Ok(())
+    }
+}
+
+/// A `Result` or `Option` value which may be a failure, `Err` or `None`.
+#[doc(hidden)]
+pub trait Fallible {
+    /// Return true if this is a failure.
+    fn failed(&self) -> bool;
+
+    /// Format the failure, e.g. `Err(e)` via `Debug` of the error, or `None`.
+    fn fmt_failure(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+impl<T, E: fmt::Debug> Fallible for Result<T, E> {
+    fn failed(&self) -> bool {
+        self.is_err()
+    }
+
+    fn fmt_failure(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Err(ref e) => write!(f, "Err({:?})", e),
+            Ok(_) => f.write_str("Ok(_)"),
+        }
+    }
+}
+
+impl<T> Fallible for Option<T> {
+    fn failed(&self) -> bool {
+        self.is_none()
+    }
+
+    fn fmt_failure(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            None => f.write_str("None"),
+            Some(_) => f.write_str("Some(_)"),
+        }
+    }
+}
+
+/// `Debug` of the failure of a `Fallible` value, without requiring `Debug` of
+/// the success type.
+#[doc(hidden)]
+pub struct Failure<'a, F: 'a>(pub &'a F);
+
+impl<'a, F: Fallible> fmt::Debug for Failure<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_failure(f)
+    }
+}
+
+/// `Display` of an error and its `source()` chain, on one line separated by
+/// `": "`, or with each cause on an indented line, and with an optional
+/// backtrace of the log site. The backtrace is captured once, when first
+/// formatted (only if the level is enabled), and reused thereafter.
+#[doc(hidden)]
+pub struct Chain<'a> {
+    err: &'a dyn fmt::Display,
+    source: Option<&'a dyn Error>,
+    multiline: bool,
+    backtrace: bool,
+    #[cfg(tao_log_backtrace)]
+    captured: RefCell<Option<String>>,
+}
+
+impl<'a> Chain<'a> {
+    pub fn new(
+        err: &'a dyn fmt::Display,
+        source: Option<&'a dyn Error>,
+        multiline: bool,
+        backtrace: bool)
+        -> Chain<'a>
+    {
+        Chain {
+            err, source, multiline, backtrace,
+            #[cfg(tao_log_backtrace)]
+            captured: RefCell::new(None),
+        }
+    }
+
+    #[cfg(tao_log_backtrace)]
+    #[allow(clippy::incompatible_msrv)] // per build.rs rustc version check
+    fn fmt_backtrace(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.backtrace {
+            let mut captured = self.captured.borrow_mut();
+            let bt = captured.get_or_insert_with(|| {
+                std::backtrace::Backtrace::force_capture().to_string()
+            });
+            write!(f, "\nlog site backtrace:\n{}", bt)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(tao_log_backtrace))]
+    fn fmt_backtrace(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Chain<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.err)?;
+        let mut next = self.source;
+        while let Some(e) = next {
+            if self.multiline {
+                write!(f, "\n  caused by: {}", e)?;
+            } else {
+                write!(f, ": {}", e)?;
+            }
+            next = e.source();
+        }
+        self.fmt_backtrace(f)
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Recorded values of sub-expressions of a condition, for power-assert style
+//! messages.
+
+use std::fmt;
+
+/// Sub-expressions of a condition and their values, as recorded by the
+/// expansion of the proc-macro `fatal_assert!`.
+///
+/// `Display` writes each sub-expression below the root (depth 0) on a new
+/// line, indented by two spaces per level of depth, e.g. `"\n  a.len() → 3"`.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Explain {
+    nodes: Vec<(usize, &'static str, String)>,
+}
+
+impl Explain {
+    pub fn new() -> Explain {
+        Explain { nodes: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Remove all nodes recorded at or after the given position.
+    pub fn truncate(&mut self, at: usize) {
+        self.nodes.truncate(at)
+    }
+
+    /// Record a node at the given position, allowing a parent to precede its
+    /// children, once its own value is known. A node without a value, not
+    /// implementing `Debug`, is omitted.
+    pub fn record(
+        &mut self,
+        at: usize,
+        depth: usize,
+        text: &'static str,
+        value: Option<String>)
+    {
+        if let Some(value) = value {
+            self.nodes.insert(at, (depth, text, value));
+        }
+    }
+}
+
+/// Reference to a recorded value, formatted via `Debug` if implemented, by
+/// autoref specialization: With both `DebugValue` and `OtherValue` in scope,
+/// `(&Probe(v)).explain_value()` resolves to the former if the type of `v`
+/// implements `Debug`, and otherwise to the latter, without a `Debug` bound.
+#[doc(hidden)]
+pub struct Probe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait DebugValue {
+    fn explain_value(&self) -> Option<String>;
+}
+
+impl<'a, T: fmt::Debug + ?Sized> DebugValue for Probe<'a, T> {
+    fn explain_value(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+
+#[doc(hidden)]
+pub trait OtherValue {
+    fn explain_value(&self) -> Option<String>;
+}
+
+impl<'a, 'b, T: ?Sized> OtherValue for &'b Probe<'a, T> {
+    fn explain_value(&self) -> Option<String> {
+        None
+    }
+}
+
+impl fmt::Display for Explain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &(depth, text, ref value) in &self.nodes {
+            if depth > 0 {
+                write!(
+                    f, concat!("\n{:w$}{} ", __tao_arrow!(), " {}"),
+                    "", text, value, w = depth * 2
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Logging extension traits for `Result` and `Option`.
+
+use std::fmt;
+
+use log::{Level, Record};
+
+use crate::fatal::{install_hook, terminate, FatalError};
+
+// Target of all records logged by the extension traits, as the module path of
+// the caller is not available.
+const TARGET: &str = module_path!();
+
+/// Logging extension methods for `Result`.
+///
+/// Records are logged with target `"tao_log::ext"` and, with rustc 1.46+,
+/// the file and line of the caller (via `#[track_caller]`).
+///
+/// # Example
+///
+/// ```rust
+/// use tao_log::ext::LogResultExt;
+/// use log::Level;
+///
+/// let r: Result<u8, std::num::ParseIntError> = "7".parse();
+/// let v = r.log_err(Level::Warn).log_ok(Level::Trace)
+///     .unwrap_or_fatal("parsing seven");
+/// assert_eq!(v, 7);
+/// ```
+pub trait LogResultExt<T, E> {
+    /// Return the `Ok` value, or if `Err`, log the context and error at the
+    /// error level, flush the logger and terminate as with `fatal!`. Like
+    /// `Result::expect`, the error is formatted with `Debug`, e.g.
+    /// `"context: ParseIntError { kind: InvalidDigit }"`.
+    fn unwrap_or_fatal(self, context: &str) -> T where E: fmt::Debug;
+
+    /// If `Err`, log its `Debug` format, e.g. `"Err(NotFound)"`, at the given
+    /// level. Returns self.
+    fn log_err(self, level: Level) -> Self where E: fmt::Debug;
+
+    /// If `Ok`, log its `Debug` format, e.g. `"Ok(7)"`, at the given level.
+    /// Returns self.
+    fn log_ok(self, level: Level) -> Self where T: fmt::Debug;
+}
+
+/// Logging extension methods for `Option`.
+///
+/// Records are logged with target `"tao_log::ext"` and, with rustc 1.46+,
+/// the file and line of the caller (via `#[track_caller]`).
+pub trait LogOptionExt<T> {
+    /// Return the `Some` value, or if `None`, log the context at the error
+    /// level, flush the logger and terminate as with `fatal!`.
+    fn unwrap_or_fatal(self, context: &str) -> T;
+
+    /// If `None`, log `"None"` at the given level. Returns self.
+    fn log_none(self, level: Level) -> Self;
+}
+
+impl<T, E> LogResultExt<T, E> for Result<T, E> {
+    #[cfg_attr(tao_log_track_caller, track_caller)]
+    fn unwrap_or_fatal(self, context: &str) -> T where E: fmt::Debug {
+        match self {
+            Ok(v) => v,
+            Err(e) => fatal(caller(), format_args!("{}: {:?}", context, e)),
+        }
+    }
+
+    #[cfg_attr(tao_log_track_caller, track_caller)]
+    fn log_err(self, level: Level) -> Self where E: fmt::Debug {
+        if let Err(ref e) = self {
+            log(caller(), level, format_args!("Err({:?})", e));
+        }
+        self
+    }
+
+    #[cfg_attr(tao_log_track_caller, track_caller)]
+    fn log_ok(self, level: Level) -> Self where T: fmt::Debug {
+        if let Ok(ref v) = self {
+            log(caller(), level, format_args!("Ok({:?})", v));
+        }
+        self
+    }
+}
+
+impl<T> LogOptionExt<T> for Option<T> {
+    #[cfg_attr(tao_log_track_caller, track_caller)]
+    fn unwrap_or_fatal(self, context: &str) -> T {
+        match self {
+            Some(v) => v,
+            None => fatal(caller(), format_args!("{}", context)),
+        }
+    }
+
+    #[cfg_attr(tao_log_track_caller, track_caller)]
+    fn log_none(self, level: Level) -> Self {
+        if self.is_none() {
+            log(caller(), level, format_args!("None"));
+        }
+        self
+    }
+}
+
+type Location = Option<(&'static str, u32)>;
+
+// Return the file and line of the caller, if available.
+#[cfg(tao_log_track_caller)]
+#[allow(clippy::incompatible_msrv)] // per build.rs rustc version check
+#[track_caller]
+fn caller() -> Location {
+    let l = std::panic::Location::caller();
+    Some((l.file(), l.line()))
+}
+
+#[cfg(not(tao_log_track_caller))]
+fn caller() -> Location {
+    None
+}
+
+fn log(loc: Location, level: Level, args: fmt::Arguments) {
+    if level <= log::STATIC_MAX_LEVEL && level <= log::max_level() {
+        log::logger().log(
+            &Record::builder()
+                .args(args)
+                .level(level)
+                .target(TARGET)
+                .file(loc.map(|l| l.0))
+                .line(loc.map(|l| l.1))
+                .build()
+        );
+    }
+}
+
+// As per `fatal!`, with panic location of the caller via `#[track_caller]`.
+#[cfg_attr(tao_log_track_caller, track_caller)]
+#[allow(unknown_lints, non_fmt_panics)] // payload, not a message
+fn fatal(loc: Location, args: fmt::Arguments) -> ! {
+    log(loc, Level::Error, args);
+    log::logger().flush();
+    terminate(None);
+    install_hook();
+    panic!(FatalError::new(
+        args, TARGET, None, loc.map(|l| l.0), loc.map(|l| l.1)
+    ));
+}
@@ -0,0 +1,215 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Process-wide termination strategy and panic payload of `fatal!`.
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::panic;
+use std::process;
+use std::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
+use std::sync::Once;
+use std::thread;
+
+/// Termination strategy of `fatal!`, after its message is logged and the
+/// logger flushed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FatalHandler {
+    /// Panic with the same message (the default). Note that a panic may be
+    /// caught, e.g. via `std::panic::catch_unwind` in a thread pool, and
+    /// doesn't otherwise stop the process unless it occurs on the main thread.
+    Panic,
+
+    /// Exit the process via `std::process::exit` with the given code. No
+    /// destructors are run.
+    Exit(i32),
+
+    /// Abort the process via `std::process::abort`.
+    Abort,
+}
+
+impl Default for FatalHandler {
+    fn default() -> FatalHandler {
+        FatalHandler::Panic
+    }
+}
+
+const PANIC: usize = 0;
+const EXIT: usize = 1;
+const ABORT: usize = 2;
+
+static KIND: AtomicUsize = AtomicUsize::new(PANIC);
+static CODE: AtomicIsize = AtomicIsize::new(0);
+
+/// Set the process-wide termination strategy of `fatal!`.
+///
+/// This applies to all subsequent uses of `fatal!` without an `exit:` code,
+/// on all threads.
+pub fn set_fatal_handler(handler: FatalHandler) {
+    match handler {
+        FatalHandler::Panic => KIND.store(PANIC, Ordering::SeqCst),
+        FatalHandler::Exit(code) => {
+            CODE.store(code as isize, Ordering::SeqCst);
+            KIND.store(EXIT, Ordering::SeqCst);
+        }
+        FatalHandler::Abort => KIND.store(ABORT, Ordering::SeqCst),
+    }
+}
+
+/// Return the current termination strategy of `fatal!`.
+pub fn fatal_handler() -> FatalHandler {
+    match KIND.load(Ordering::SeqCst) {
+        EXIT => FatalHandler::Exit(CODE.load(Ordering::SeqCst) as i32),
+        ABORT => FatalHandler::Abort,
+        _ => FatalHandler::Panic,
+    }
+}
+
+// Terminate the process for `fatal!`, via the given exit code, or otherwise
+// the current handler, or return if the handler is to panic, which is left to
+// the macro for the location of the panic.
+#[doc(hidden)]
+pub fn terminate(exit: Option<i32>) {
+    match exit.map_or_else(fatal_handler, FatalHandler::Exit) {
+        FatalHandler::Panic => {}
+        FatalHandler::Exit(code) => process::exit(code),
+        FatalHandler::Abort => process::abort(),
+    }
+}
+
+/// Panic payload of `fatal!`, distinguishing a deliberate fatal from other
+/// panics.
+///
+/// A supervisor catching a panic, e.g. via `std::panic::catch_unwind`, may
+/// use [`from_payload`](#method.from_payload) to test for and access this.
+/// Since the default panic hook only prints string payloads, `fatal!` first
+/// installs a hook printing its message, via
+/// [`install_hook`](fn.install_hook.html).
+///
+/// # Example
+///
+/// ```rust
+/// use std::panic;
+/// use tao_log::fatal;
+/// use tao_log::fatal::FatalError;
+///
+/// # panic::set_hook(Box::new(|_| {}));
+/// let res = panic::catch_unwind(|| {
+///     fatal!(target: "reactor", "core breach in {}ms", 100);
+/// });
+/// let payload = res.unwrap_err();
+/// let fe = FatalError::from_payload(&payload).unwrap();
+/// assert_eq!(fe.message(), "core breach in 100ms");
+/// assert_eq!(fe.target(), "reactor");
+/// ```
+#[derive(Clone, Debug)]
+pub struct FatalError {
+    message: String,
+    target: String,
+    module_path: Option<&'static str>,
+    file: Option<&'static str>,
+    line: Option<u32>,
+}
+
+impl FatalError {
+    // Constructor for `fatal!`.
+    #[doc(hidden)]
+    pub fn new(
+        message: fmt::Arguments,
+        target: &str,
+        module_path: Option<&'static str>,
+        file: Option<&'static str>,
+        line: Option<u32>)
+        -> FatalError
+    {
+        FatalError {
+            message: message.to_string(),
+            target: target.to_owned(),
+            module_path, file, line
+        }
+    }
+
+    /// Return the `FatalError` of a panic payload, if it is one.
+    ///
+    /// This accepts either the payload as borrowed from a `PanicInfo`, or as
+    /// returned boxed from `catch_unwind`.
+    pub fn from_payload(payload: &(dyn Any + Send)) -> Option<&FatalError> {
+        if let Some(b) = payload.downcast_ref::<Box<dyn Any + Send>>() {
+            FatalError::from_payload(&**b)
+        } else {
+            payload.downcast_ref::<FatalError>()
+        }
+    }
+
+    /// The formatted message, as also logged.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The target, as also logged.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// The module path of the `fatal!` use, if known.
+    pub fn module_path(&self) -> Option<&'static str> {
+        self.module_path
+    }
+
+    /// The source file of the `fatal!` use, if known.
+    pub fn file(&self) -> Option<&'static str> {
+        self.file
+    }
+
+    /// The source line of the `fatal!` use, if known.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+}
+
+impl fmt::Display for FatalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for FatalError {}
+
+/// Install a panic hook printing the message of `fatal!` panics to stderr.
+///
+/// The default panic hook only prints string payloads, such that the
+/// [`FatalError`](struct.FatalError.html) of a `fatal!` panic would be
+/// printed as `Box<dyn Any>`. The installed hook first prints its message, as
+/// `fatal: <message>`, then always chains to the prior hook, which still
+/// prints the thread and location of every panic. Only the first call has
+/// effect.
+///
+/// This is called by `fatal!` before it panics, so only needs to be called
+/// to install the hook earlier, e.g. with a custom hook set later, which
+/// should then chain to it. It has no effect while panicking, where the hook
+/// can't be changed.
+///
+/// # Example
+///
+/// ```rust
+/// use tao_log::fatal;
+///
+/// fatal::install_hook();
+/// ```
+pub fn install_hook() {
+    static INSTALL: Once = Once::new();
+    if thread::panicking() {
+        return;
+    }
+    INSTALL.call_once(|| {
+        let prior = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if let Some(fe) = FatalError::from_payload(info.payload()) {
+                eprintln!("fatal: {}", fe);
+            }
+            prior(info);
+        }));
+    });
+}
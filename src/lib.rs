@@ -33,7 +33,13 @@
 //! ```rust
 //! use tao_log::*;
 //! ```
-//! Or if desired, import the individual macros:
+//! This glob import includes the macros, the re-exported `log` crate, and the
+//! modules of supporting types and traits: [`ext`],
+//! [`fatal`](fatal/index.html), [`panic_hook`](panic_hook/index.html),
+//! [`scope`] and (with the `testing` feature)
+//! [`testing`](testing/index.html), none of which shadow a `std` module, as
+//! well as the hidden internals of the macros. Or if desired, import the
+//! individual macros:
 //!
 //! ```rust
 //! use tao_log::{debug, debugv, error, warn};
@@ -61,7 +67,14 @@
 //!
 //! To these formatted logging macros, _tao-log_ adds a
 //! [`fatal!`](macro.fatal.html) macro, which logs at the _error_ level, and
-//! then uses the same message to `panic!`.
+//! then panics with the same message, as a
+//! [`FatalError`](fatal/struct.FatalError.html) payload. Alternatively, with
+//! [`set_fatal_handler`](fatal/fn.set_fatal_handler.html) or an `exit:` code,
+//! `fatal!` exits or aborts the process after logging. The `fatal_assert!`
+//! and `fatal_assert_eq!` macros similarly log a failed assertion with the
+//! values of its operands. To also log all other
+//! panics, e.g. from `unwrap()` in dependencies, see
+//! [`panic_hook::install_hook`](panic_hook/fn.install_hook.html).
 //!
 //! ### Testing for output
 //!
@@ -90,7 +103,7 @@
 //! ### Inline expression and value logging
 //!
 //! The _-v_ macros support inline expression and value logging, as a superset
-//! of the [`std::dbg!`] macro, for use with the logging system. A _single_
+//! of the [`std::dbg!`] macro, for use with the logging system. Each
 //! expression argument is evaluated exactly once, regardless of if the
 //! logging level is enabled, and its value is returned from the macro. Given
 //! this code as a starting point:
@@ -144,6 +157,209 @@
 //! infov!("index", "{:#?}", i); // pretty multi-line format (for structs)
 //! ```
 //!
+//! A literal prefix is concatenated into the format string at compile time.
+//! For a prefix only known at runtime, a leading `prefix = <expr>` argument
+//! accepts any `Display` value, which is evaluated and formatted only if the
+//! log record is enabled:
+//!
+//! ```rust
+//! use tao_log::*;
+//!
+//! let ctx = format!("request {}", 7);
+//! let i = 32;
+//! warnv!(prefix = &ctx, i);
+//! // ^------------------------ warn log: request 7 i → 32
+//! warnv!(prefix = &ctx, "{:#x}", i);
+//! // ^------------------------ warn log: request 7 i → 0x20
+//! ```
+//!
+//! Like `std::dbg!`, multiple expressions may be given, in which case they are
+//! evaluated in order and logged together in a single record, and the tuple of
+//! their values is returned. A leading literal is always interpreted as the
+//! prefix, and a second literal as the value format, which then applies to
+//! each value:
+//!
+//! ```rust
+//! use tao_log::*;
+//!
+//! let (i, s) = (32, "foo");
+//! let (i, s) = debugv!(i, s);
+//! // ^------------------------ debug log: i → 32, s → "foo"
+//! let (q, r) = debugv!("split", "{}", i / 5, i % 5);
+//! // ^------------------------ debug log: split i / 5 → 6, i % 5 → 2
+//! # assert_eq!((q, r), (6, 2));
+//! # assert_eq!(s, "foo");
+//! ```
+//!
+//! Any expression may also be named, as `<name> = <expr>`, in which case the
+//! name is logged instead of the (possibly long) stringified expression:
+//!
+//! ```rust
+//! use tao_log::*;
+//! # struct Stats { idle: u32 }
+//! # let stats = Stats { idle: 3 };
+//!
+//! let idle = debugv!("pool", idle = stats.idle);
+//! // ^------------------------ debug log: pool idle → 3
+//! # assert_eq!(idle, 3);
+//! ```
+//!
+//! Since the -v macros always evaluate their expression, the companion _-l_
+//! macros (e.g. `debugl!`) are instead lazy, for expensive diagnostic
+//! computations: these take the same arguments, but only evaluate and log the
+//! expression(s) if `log_enabled!`, returning `Some` value, or otherwise
+//! `None`:
+//!
+//! ```rust
+//! use tao_log::*;
+//! # fn analyze(a: u32) -> u32 { a }
+//! # let asteroid = 1;
+//!
+//! let mass: Option<u32> = debugl!("asteroid", analyze(asteroid)); // expensive!
+//! // ^------------------------ if enabled, debug log: asteroid analyze(asteroid) → 1
+//! ```
+//!
+//! With the optional `kv` feature (enabling the _log_ crate `kv` feature),
+//! the _-v_ macros also attach structured key-value pairs to the log record,
+//! for use by structured (e.g. JSON) loggers. Each `stringify!`-ed expression
+//! is a key, with its value captured via `Debug`, or via `Display` of the
+//! value as formatted with any custom value format. Any prefix is included
+//! with the `prefix` key. The message remains as above. This feature requires
+//! _log_ 0.4.21+, and thus rustc 1.60.0+, but the _log_ dependency itself is
+//! left at its minimum for the MSRV, so with an older _log_ locked, `cargo
+//! update -p log` is needed.
+//!
+//! With the optional `proc-macro` feature (rustc 1.45.0+), the prefix and
+//! value format literals are validated at compile time, such that a prefix
+//! must be a string without placeholders, and the value format must contain
+//! exactly one placeholder. An empty `""` prefix is then equivalent to no
+//! prefix.
+//!
+//! The `proc-macro` feature also accepts a leading `template = "<template>"`
+//! argument to the _-v_ macros, replacing the default message format with a
+//! template of `{prefix}`, `{expr}` and `{value}` slots, validated at compile
+//! time. The `{value}` slot is formatted per any value format (default
+//! `{:?}`). For multiple expressions, the template from the first to the last
+//! `{expr}` or `{value}` slot is repeated for each, separated by `", "`:
+//!
+//! ```rust
+//! # #[cfg(feature = "proc-macro")] {
+//! use tao_log::*;
+//!
+//! let (i, j) = (32, 19);
+//! debugv!(template = "{expr} = {value}", i);
+//! // ^------------------------ debug log: i = 32
+//! debugv!(template = "{prefix}: {expr}={value}", "ij", "{:x}", i, j);
+//! // ^------------------------ debug log: ij: i=20, j=13
+//! # }
+//! ```
+//!
+//! To instead only replace the `→` (and `←`) arrows of all messages with
+//! ASCII `->` (and `<-`), e.g. for log parsers or terminals which don't handle
+//! them, enable the optional `ascii-arrow` feature. Note this feature is
+//! non-additive: Via cargo's feature unification, it changes the messages
+//! logged by all crates of a build using _tao-log_, including any
+//! dependencies, so it should only be enabled by the final binary, not by
+//! libraries.
+//!
+//! The `proc-macro` feature also provides the `#[logfn]` attribute, which logs
+//! the arguments of a function on entry and its return value on exit, in the
+//! same format, e.g. `add(a → 1, b → 2)` and `add → 3`. Its `logv_check!`
+//! macro and per-level variants (e.g. `debugv_check!`) log a boolean
+//! condition with the value of each evaluated sub-expression of its `&&`,
+//! `||` and comparison tree, returning the value, e.g. for use in an `if`.
+//!
+//! ### Logging results
+//!
+//! The `logv_result!` macro and its per-level variants (e.g. `debugv_result!`)
+//! log a `Result` expression like the _-v_ macros, but with an `Ok` value at
+//! the given level and an `Err` value at the error level (or as specified
+//! with `err:`), returning the `Result` unchanged, for use with `?`:
+//!
+//! ```rust
+//! use tao_log::*;
+//!
+//! fn parse(s: &str) -> Result<u32, std::num::ParseIntError> {
+//!     let n = debugv_result!(sources, s.parse::<u32>())?;
+//!     //      ^-- debug log: s.parse::<u32>() → Ok(42)
+//!     Ok(n * 2)
+//! }
+//! # assert_eq!(parse("42"), Ok(84));
+//! ```
+//!
+//! With the `sources` marker, the `source()` chain of an `Err` value is
+//! appended to the message, e.g. `: inner: root`.
+//!
+//! The `tryv!` macro is instead a logging `?` operator, which only logs an
+//! `Err` or `None` value (at the error level by default), before returning
+//! early as with `?`. This preserves the context of the failed expression and
+//! its location of use:
+//!
+//! ```rust
+//! use tao_log::*;
+//!
+//! fn parse(s: &str) -> Result<u32, Box<dyn std::error::Error>> {
+//!     let n: u32 = tryv!("parsing", s.parse());
+//!     //           ^-- on failure, error log:
+//!     //               parsing s.parse() → Err(ParseIntError { kind: InvalidDigit })
+//!     Ok(n * 2)
+//! }
+//! # assert!(parse("x").is_err());
+//! ```
+//!
+//! To log an error value itself with its full `source()` chain, the
+//! `log_chain!` macro and its per-level variants (e.g. `error_chain!`) log
+//! the chain on one line, e.g. `while syncing: request failed: connection
+//! reset`, or with the `multiline` marker, each cause on an indented line:
+//!
+//! ```rust
+//! use tao_log::*;
+//!
+//! # let res: Result<(), std::fmt::Error> = Err(std::fmt::Error);
+//! if let Err(e) = res {
+//!     error_chain!(target: "sync", "while syncing", e);
+//! }
+//! ```
+//!
+//! ### Timing expressions
+//!
+//! The `logv_time!` macro and its per-level variants (e.g. `debugv_time!`)
+//! also log the wall-clock time taken to evaluate the expression, e.g.
+//! `work() → 3 (in 12.3ms)`, returning its value. With the `novalue` marker,
+//! only the expression and elapsed time are logged.
+//!
+//! The `slowv!` macro instead only logs when the elapsed time exceeds a given
+//! threshold, at the warn level by default, e.g. for latency budgets on hot
+//! paths: `slowv!(Duration::from_millis(10), query())`.
+//!
+//! ### Logging scopes
+//!
+//! The `scopev!` macro logs entry to a named scope and returns a [`Scope`]
+//! guard which logs exit and the elapsed time when dropped, with nested
+//! scopes of the same thread indented as a call tree:
+//!
+//! ```rust
+//! use tao_log::*;
+//!
+//! fn handle() {
+//!     let _s = scopev!("handle");
+//!     // ^-- debug log: → handle
+//!     // ...
+//! }   // ^-- debug log: ← handle (in 12.3ms)
+//! # handle();
+//! ```
+//!
+//! ### Limiting repetition
+//!
+//! To avoid flooding logs from loops and other repeated paths, `_once`
+//! suffixed macros (e.g. `warn_once!`, `debugv_once!`) log only the first
+//! time their location of use is reached, and `_every_n` suffixed macros
+//! (e.g. `warn_every_n!(10, …)`, `debugv_every_n!(10, …)`) log only every
+//! `n`th time. Similarly, `_throttled` suffixed macros (e.g.
+//! `warn_throttled!(Duration::from_secs(10), …)`) log at most once per time
+//! interval, noting the count of suppressed messages. The -v forms still
+//! evaluate and return the expression every time.
+//!
 //! ### Specifying the logging target
 //!
 //! For _all_ logging macros, the _target_ defaults to the module path of the
@@ -165,10 +381,24 @@
 //! # }
 //! ```
 //!
+//! ### Testing
+//!
+//! With the optional `testing` feature, the `testing` module provides a
+//! capture logger for tests, recording the level, target, message, location
+//! and any key-value pairs of each record, per thread, such that tests run
+//! in parallel don't interfere. Assertion macros such as `assert_logged!` and
+//! `assert_logged_in_order!` check the captured records. With the
+//! `proc-macro` feature as well, the `#[testing::test]` attribute runs a test
+//! with a capture, printing the captured records only on failure, and
+//! supports checks of the records logged by `should_panic` tests.
+//!
 //! [PR]: https://github.com/rust-lang-nursery/log/pull/316
 //! [RFC 317]: https://github.com/rust-lang-nursery/log/pull/317
 //! [`std::fmt`]: https://doc.rust-lang.org/stable/std/fmt/index.html
 //! [`std::dbg!`]: https://doc.rust-lang.org/std/macro.dbg.html
+//! [`Scope`]: scope/struct.Scope.html
+//! [`ext`]: ext/index.html
+//! [`scope`]: scope/index.html
 
 #![doc(html_logo_url = "http://gravitext.com/svg/yin_yang.svg")]
 
@@ -176,4 +406,36 @@ pub use ::log;
 
 pub use log::{debug, error, info, log, log_enabled, trace, warn};
 
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+pub use tao_log_macros::__tao_logv_impl;
+
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+pub use tao_log_macros::{__tao_assert_impl, __tao_checkv_impl};
+
+#[cfg(feature = "proc-macro")]
+pub use tao_log_macros::logfn;
+
+// Supporting items of the exported macros, kept out of the crate root and
+// thus the `use tao_log::*` glob import.
+#[doc(hidden)]
+pub mod __private {
+    pub use std::error::Error;
+    pub use crate::error::{Chain, Fallible, Failure, Sources};
+    pub use crate::explain::{DebugValue, Explain, OtherValue, Probe};
+    pub use crate::fatal::terminate;
+    pub use crate::throttle::Throttle;
+}
+
 #[macro_use] mod macros;
+mod error;
+mod explain;
+pub mod ext;
+pub mod fatal;
+pub mod panic_hook;
+pub mod scope;
+mod throttle;
+
+#[cfg(feature = "testing")]
+pub mod testing;
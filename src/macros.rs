@@ -11,10 +11,26 @@
 /// message to panic.
 ///
 /// This will duplicate the message, once via the registered logger, then
-/// again via stderr for the panic (default handler). Since this is a fatal
-/// and presumably serious condition, potential duplication is of less concern
-/// than the risk of missing the message. This will always `panic!`, even if
-/// no logger is configured, or if error level messages aren't logged.
+/// again via stderr for the panic, as `fatal: <message>`. Since this is a
+/// fatal and presumably serious condition, potential duplication is of less
+/// concern than the risk of missing the message. This will always `panic!`,
+/// even if no logger is configured, or if error level messages aren't logged.
+///
+/// Instead of panicking, the process may be terminated via the process-wide
+/// handler set by [`set_fatal_handler`], or via an `exit:` code given for a
+/// single use, e.g. `fatal!(exit: 3, "no config")`, after the message is
+/// logged and the logger flushed.
+///
+/// The panic payload is a [`FatalError`], which may be distinguished from
+/// other panics when caught. As the default panic hook only prints string
+/// payloads, the first panic installs [`fatal::install_hook`], printing the
+/// message then chaining to the prior (e.g. default) hook. Note that a
+/// `#[should_panic(expected = "…")]` test only matches string payloads, but
+/// `#[testing::test]` also matches this payload.
+///
+/// [`set_fatal_handler`]: fatal/fn.set_fatal_handler.html
+/// [`FatalError`]: fatal/struct.FatalError.html
+/// [`fatal::install_hook`]: fatal/fn.install_hook.html
 ///
 /// # Example
 ///
@@ -25,15 +41,92 @@
 /// # let td = Duration::new(0, 100_000_000);
 /// fatal!("shields compromised, core breach in {:?}!", td);
 /// // ^1 -- error level message: shields compromised, core breach in 100ms!
-/// // ^2 -- panic:               shields compromised, core breach in 100ms!
+/// // ^2 -- panic:        fatal: shields compromised, core breach in 100ms!
 /// ```
 #[macro_export]
 macro_rules! fatal {
+    (target: $target:expr, exit: $code:expr, $($arg:tt)+) => (
+        $crate::__tao_fatal!($target, Some($code), $($arg)+)
+    );
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_fatal!($target, None, $($arg)+)
+    );
+    (exit: $code:expr, $($arg:tt)+) => (
+        $crate::__tao_fatal!(module_path!(), Some($code), $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_fatal!(module_path!(), None, $($arg)+)
+    );
+}
+
+/// Assert that a condition is true, or otherwise log it and the values of its
+/// operands at the error level, flush the logger and panic, as with
+/// `fatal!`.
+///
+/// Logs with the optional or default (module path of use) target, the
+/// condition, and an optional format string and arguments appended to the
+/// message. On success, only the condition is evaluated.
+///
+/// With the `proc-macro` feature, the values of the sub-expressions of a
+/// condition composed of `&&`, `||` and comparisons that explain the failure
+/// are logged in the same record, one per line and indented as a tree. The
+/// operands of comparisons are borrowed, and logged only if they implement
+/// `Debug`. Each sub-expression is evaluated once, preserving short circuit
+/// semantics, except that a failed condition containing `||` is evaluated a
+/// second time to record the values, as nothing is recorded on success.
+/// Otherwise only the condition is logged.
+///
+/// # Example
+///
+/// ```rust,should_panic
+/// use tao_log::fatal_assert;
+///
+/// let limit = 2;
+/// let v = vec![1, 2, 3];
+/// let flag = true;
+/// fatal_assert!(v.len() <= limit && flag, "while checking");
+/// // ^-- error level message, with the proc-macro feature:
+/// //     assertion failed: v.len() <= limit && flag: while checking
+/// //       v.len() <= limit → false
+/// //         v.len() → 3
+/// //         limit → 2
+/// ```
+#[macro_export]
+macro_rules! fatal_assert {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_fatal_assert!($target, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_fatal_assert!(module_path!(), $($arg)+)
+    );
+}
+
+/// Assert that two expressions are equal, or otherwise log both and their
+/// values at the error level, flush the logger and panic, as with `fatal!`.
+///
+/// Logs with the optional or default (module path of use) target, and an
+/// optional format string and arguments appended to the message. Both
+/// expressions are borrowed and must implement `Debug`.
+///
+/// # Example
+///
+/// ```rust,should_panic
+/// use tao_log::fatal_assert_eq;
+///
+/// let (a, b) = (1, 2);
+/// fatal_assert_eq!(a + 1, b * 2);
+/// // ^-- error level message:
+/// //     assertion failed: a + 1 == b * 2
+/// //       a + 1 → 2
+/// //       b * 2 → 4
+/// ```
+#[macro_export]
+macro_rules! fatal_assert_eq {
     (target: $target:expr, $($arg:tt)+) => (
-        $crate::__tao_fatal!($target, $($arg)+)
+        $crate::__tao_fatal_assert_eq!($target, $($arg)+)
     );
     ($($arg:tt)+) => (
-        $crate::__tao_fatal!(module_path!(), $($arg)+)
+        $crate::__tao_fatal_assert_eq!(module_path!(), $($arg)+)
     );
 }
 
@@ -41,15 +134,34 @@ macro_rules! fatal {
 ///
 /// Logs with the optional or default (module path of use) target, specified
 /// `Level`, optional prefix, and optional or default (`"{:?}"`) value format
-/// string, and a single expression. The expression argument is evaluated
+/// string, and one or more expressions. Each expression argument is evaluated
 /// exactly once, regardless of if the logging level is enabled, and its
-/// value is returned from the macro. This is normally only used through the
-/// _-v_ macros like `debugv!` or `tracev!`.
+/// value is returned from the macro. With multiple expressions, these are
+/// logged in a single record and the tuple of values is returned. This is
+/// normally only used through the _-v_ macros like `debugv!` or `tracev!`.
 ///
 /// Note that the value is moved and then returned. If the type does not
 /// implement `Copy`, ownership may be retained by borrowing by reference
 /// e.g. `debugv!(&expr)`.
 ///
+/// A leading literal argument is always interpreted as the prefix, and a
+/// following literal as the value format. To log a literal value in the
+/// first position, wrap it in parentheses, e.g. `debugv!((1), i)`.
+///
+/// Each expression may be named, as `<name> = <expr>`, to log the name
+/// instead of the stringified expression, e.g. `debugv!(idle = stats.idle)`.
+/// To instead log an assignment expression, wrap it in parentheses, e.g.
+/// `debugv!((a = b))`.
+///
+/// A leading `prefix = <expr>` argument is instead a runtime prefix of any
+/// `Display` type, evaluated only if the level is enabled, which may also be
+/// followed by a literal value format, e.g. `debugv!(prefix = &ctx, "{}", i)`.
+///
+/// With the `proc-macro` feature, a `template = "<template>"` argument may
+/// precede any prefix, replacing the default message format, e.g.
+/// `debugv!(template = "{expr} = {value}", i)`. See the crate documentation
+/// for the template slots.
+///
 /// # Examples
 ///
 /// ```rust
@@ -109,58 +221,2285 @@ macro_rules! tracev {
     ($($arg:tt)+) => ($crate::__tao_logv!($crate::log::Level::Trace, $($arg)+))
 }
 
-// Helper macro for `fatal!`
-#[doc(hidden)]
+/// Log an expression and its value at any specified level, only evaluating
+/// it if logging is enabled, returning `Some` value or `None`.
+///
+/// This takes the same arguments as `logv!`, but is lazy: The target and
+/// level are first checked via `log_enabled!`, and only if enabled are the
+/// expression(s) evaluated and logged, returning `Some` of the value (or
+/// tuple of values). Otherwise `None` is returned. This is useful for
+/// expensive diagnostic computations, as the `Option` makes explicit that the
+/// expression may not be evaluated. This is normally only used through the
+/// _-l_ macros like `debugl!` or `tracel!`.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+///
+/// fn summary(v: &[u32]) -> u32 { v.iter().sum() }
+///
+/// let v = vec![1, 2, 3];
+/// let s: Option<u32> = debugl!("state", summary(&v));
+/// // ^-- if enabled, debug level message: "state summary(&v) → 6"
+/// # assert!(s.is_none() || s == Some(6));
+/// logl!(target: "special", log::Level::Trace, v.len(), summary(&v));
+/// ```
 #[macro_export]
-macro_rules! __tao_fatal {
-    ($target:expr, $($arg:tt)+) => (
-        match format_args!($($arg)+) {
-            args => {
-                $crate::error!(target: $target, "{}", args);
-                $crate::log::logger().flush();
-                panic!("{}", args);
-            }
-        }
+macro_rules! logl {
+    (target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_logl!($target, $lvl, $($arg)+)
+    );
+    ($lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_logl!(module_path!(), $lvl, $($arg)+)
     );
 }
 
-// Helper macro for the -v macros, handling the permutations of optional
-// parameters. Note: The required level parameter is first here for
-// convenience of internal use with variable-args.
-#[doc(hidden)]
+/// Log an expression at the error level, only if enabled, returning `Some`
+/// value or `None`.
 #[macro_export]
-macro_rules! __tao_logv {
-    ($lvl:expr, target: $tgt:expr, $pre:expr, $vfmt:expr, $exp:expr $(,)?) => (
-        $crate::__tao_v_eval!($tgt, $lvl, concat!($pre, " {} → ", $vfmt), $exp)
+macro_rules! errorl {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_logl!($target, $crate::log::Level::Error, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_logl!(module_path!(), $crate::log::Level::Error, $($arg)+)
     );
-    ($lvl:expr, target: $tgt:expr, $pre:expr, $exp:expr $(,)?) => (
-        $crate::__tao_v_eval!($tgt, $lvl, concat!($pre, " {} → {:?}"), $exp)
+}
+
+/// Log an expression at the warn level, only if enabled, returning `Some`
+/// value or `None`.
+#[macro_export]
+macro_rules! warnl {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_logl!($target, $crate::log::Level::Warn, $($arg)+)
     );
-    ($lvl:expr, target: $tgt:expr, $exp:expr $(,)?) => (
-        $crate::__tao_v_eval!($tgt, $lvl, "{} → {:?}", $exp)
+    ($($arg:tt)+) => (
+        $crate::__tao_logl!(module_path!(), $crate::log::Level::Warn, $($arg)+)
+    );
+}
+
+/// Log an expression at the info level, only if enabled, returning `Some`
+/// value or `None`.
+#[macro_export]
+macro_rules! infol {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_logl!($target, $crate::log::Level::Info, $($arg)+)
     );
-    ($lvl:expr, $pre:expr, $vfmt:expr, $exp:expr $(,)?) => (
-        $crate::__tao_v_eval!(module_path!(), $lvl, concat!($pre, " {} → ", $vfmt), $exp)
+    ($($arg:tt)+) => (
+        $crate::__tao_logl!(module_path!(), $crate::log::Level::Info, $($arg)+)
     );
-    ($lvl:expr, $pre:expr, $exp:expr $(,)?) => (
-        $crate::__tao_v_eval!(module_path!(), $lvl, concat!($pre, " {} → {:?}"), $exp)
+}
+
+/// Log an expression at the debug level, only if enabled, returning `Some`
+/// value or `None`.
+#[macro_export]
+macro_rules! debugl {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_logl!($target, $crate::log::Level::Debug, $($arg)+)
     );
-    ($lvl:expr, $exp:expr $(,)?) => (
-        $crate::__tao_v_eval!(module_path!(), $lvl, "{} → {:?}", $exp)
+    ($($arg:tt)+) => (
+        $crate::__tao_logl!(module_path!(), $crate::log::Level::Debug, $($arg)+)
     );
 }
 
-// Inner helper macro for __tao_logv. Evaluates expression exactly once, moves
-// value and returns it.
-#[doc(hidden)]
+/// Log an expression at the trace level, only if enabled, returning `Some`
+/// value or `None`.
 #[macro_export]
-macro_rules! __tao_v_eval {
-    ($tgt:expr, $lvl:expr, $fmt:expr, $exp:expr) => (
-        match $exp {
-            vt => {
-                $crate::log!(target: $tgt, $lvl, $fmt, stringify!($exp), &vt);
-                vt
-            }
-        }
+macro_rules! tracel {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_logl!($target, $crate::log::Level::Trace, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_logl!(module_path!(), $crate::log::Level::Trace, $($arg)+)
+    );
+}
+
+/// Log a `Result` expression and its value, with `Ok` and `Err` at different
+/// levels, returning the `Result` unchanged.
+///
+/// Logs with the optional or default (module path of use) target, the
+/// specified `Level` for an `Ok` value, optional `err:` level for an `Err`
+/// value (default: error), optional `sources` marker, optional prefix, and
+/// the expression. The expression is evaluated exactly once, regardless of if
+/// the logging level is enabled, and the `Result` is returned, for example
+/// for use with the `?` operator. The `Result` is logged via its `Debug`
+/// implementation.
+///
+/// With the `sources` marker, an `Err` value is followed by its
+/// `std::error::Error::source()` chain, e.g. `"read() → Err(Outer): inner:
+/// root"`. This requires that the error type implement `Error`, or
+/// dereference to such a type, e.g. `Box<dyn Error>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+/// use log::Level;
+///
+/// fn parse(s: &str) -> Result<u32, std::num::ParseIntError> {
+///     let n = logv_result!(Level::Info, s.parse::<u32>())?;
+///     //      ^-- info level message: "s.parse::<u32>() → Ok(42)"
+///     //      ^-- or error level message:
+///     //          "s.parse::<u32>() → Err(ParseIntError { kind: InvalidDigit })"
+///     Ok(n)
+/// }
+///
+/// # fn main() {
+/// assert_eq!(parse("42"), Ok(42));
+/// assert!(parse("x").is_err());
+///
+/// let r: Result<u8, String> = Err("busy".to_owned());
+/// logv_result!(target: "io", Level::Debug, err: Level::Warn, "retry", r).ok();
+/// //      ^-- warn level message: "retry r → Err(\"busy\")"
+/// # }
+/// ```
+#[macro_export]
+macro_rules! logv_result {
+    (target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_resultv!(@err $target, $lvl, $($arg)+)
+    );
+    ($lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_resultv!(@err module_path!(), $lvl, $($arg)+)
+    );
+}
+
+/// Log a `Result` expression with `Ok` at the error level, returning it.
+#[macro_export]
+macro_rules! errorv_result {
+    ($($arg:tt)+) => (
+        $crate::__tao_resultv!(@target $crate::log::Level::Error, $($arg)+)
+    )
+}
+
+/// Log a `Result` expression with `Ok` at the warn level, returning it.
+#[macro_export]
+macro_rules! warnv_result {
+    ($($arg:tt)+) => (
+        $crate::__tao_resultv!(@target $crate::log::Level::Warn, $($arg)+)
+    )
+}
+
+/// Log a `Result` expression with `Ok` at the info level, returning it.
+#[macro_export]
+macro_rules! infov_result {
+    ($($arg:tt)+) => (
+        $crate::__tao_resultv!(@target $crate::log::Level::Info, $($arg)+)
+    )
+}
+
+/// Log a `Result` expression with `Ok` at the debug level, returning it.
+#[macro_export]
+macro_rules! debugv_result {
+    ($($arg:tt)+) => (
+        $crate::__tao_resultv!(@target $crate::log::Level::Debug, $($arg)+)
+    )
+}
+
+/// Log a `Result` expression with `Ok` at the trace level, returning it.
+#[macro_export]
+macro_rules! tracev_result {
+    ($($arg:tt)+) => (
+        $crate::__tao_resultv!(@target $crate::log::Level::Trace, $($arg)+)
+    )
+}
+
+/// Log a failed `Result` or `Option` expression, then return early from the
+/// enclosing function, like the `?` operator.
+///
+/// Logs with the optional or default (module path of use) target, optional
+/// `err:` level (default: error), optional prefix, and the expression. The
+/// expression is evaluated exactly once. If its value is an `Err` or `None`,
+/// it is logged, e.g. `"read() → Err(e)"` via the `Debug` implementation of
+/// the error, and the `?` operator is then applied, including any `From`
+/// conversion of the error. Otherwise the success value is returned without
+/// any logging.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+/// use log::Level;
+///
+/// fn total(a: &str, b: &str) -> Result<u32, Box<dyn std::error::Error>> {
+///     let a: u32 = tryv!(a.parse());
+///     let b: u32 = tryv!(target: "parse", err: Level::Warn, "second", b.parse());
+///     //           ^-- warn level message:
+///     //               "second b.parse() → Err(ParseIntError { kind: InvalidDigit })"
+///     Ok(a + b)
+/// }
+///
+/// fn first(v: &[u32]) -> Option<u32> {
+///     let f = tryv!(v.first());
+///     //      ^-- error level message: "v.first() → None"
+///     Some(*f * 2)
+/// }
+///
+/// # fn main() {
+/// assert_eq!(total("1", "2").unwrap(), 3);
+/// assert!(total("1", "x").is_err());
+/// assert_eq!(first(&[]), None);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! tryv {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_tryv!(@err $target, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_tryv!(@err module_path!(), $($arg)+)
+    );
+}
+
+/// Log an error and its `source()` chain at any specified level, returning
+/// the error.
+///
+/// Logs with the optional or default (module path of use) target, specified
+/// `Level`, optional `multiline` and `backtrace` markers, optional prefix,
+/// and the error expression. The expression is evaluated exactly once,
+/// regardless of if the logging level is enabled, and its value is returned.
+/// This requires that the error type implement `std::error::Error`, or
+/// dereference to such a type, e.g. `Box<dyn Error>` or `&dyn Error`.
+///
+/// The error and each cause are logged via `Display`, by default on one line
+/// separated by `": "`, following any prefix, e.g. `"while syncing: request
+/// failed: connection reset"`. With the `multiline` marker, each cause is
+/// instead on a separate indented line of the same record, e.g.:
+///
+/// ```text
+/// while syncing: request failed
+///   caused by: connection reset
+/// ```
+///
+/// With the `backtrace` marker and rustc 1.65+, a backtrace captured at the
+/// point of logging (not of the error's creation) is appended, following a
+/// `log site backtrace:` line. It is otherwise ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+/// use log::Level;
+///
+/// fn parse(s: &str) -> Result<u32, Box<dyn std::error::Error>> {
+///     Ok(s.parse::<u32>()?)
+/// }
+///
+/// if let Err(e) = parse("x") {
+///     error_chain!(target: "sync", "while syncing", &e);
+///     // ^-- error level message: "while syncing: invalid digit found in string"
+///     log_chain!(Level::Info, multiline, e);
+/// }
+/// ```
+#[macro_export]
+macro_rules! log_chain {
+    (target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@ml $target, $lvl, $($arg)+)
+    );
+    ($lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@ml module_path!(), $lvl, $($arg)+)
+    );
+}
+
+/// Log an error and its `source()` chain at the error level, returning the
+/// error.
+#[macro_export]
+macro_rules! error_chain {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@ml $target, $crate::log::Level::Error, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_chain!(
+            @ml module_path!(), $crate::log::Level::Error, $($arg)+
+        )
+    );
+}
+
+/// Log an error and its `source()` chain at the warn level, returning the
+/// error.
+#[macro_export]
+macro_rules! warn_chain {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@ml $target, $crate::log::Level::Warn, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_chain!(
+            @ml module_path!(), $crate::log::Level::Warn, $($arg)+
+        )
+    );
+}
+
+/// Log an error and its `source()` chain at the info level, returning the
+/// error.
+#[macro_export]
+macro_rules! info_chain {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@ml $target, $crate::log::Level::Info, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_chain!(
+            @ml module_path!(), $crate::log::Level::Info, $($arg)+
+        )
+    );
+}
+
+/// Log an error and its `source()` chain at the debug level, returning the
+/// error.
+#[macro_export]
+macro_rules! debug_chain {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@ml $target, $crate::log::Level::Debug, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_chain!(
+            @ml module_path!(), $crate::log::Level::Debug, $($arg)+
+        )
+    );
+}
+
+/// Log an error and its `source()` chain at the trace level, returning the
+/// error.
+#[macro_export]
+macro_rules! trace_chain {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@ml $target, $crate::log::Level::Trace, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_chain!(
+            @ml module_path!(), $crate::log::Level::Trace, $($arg)+
+        )
+    );
+}
+
+/// Log an expression, its value and the time taken to evaluate it, at any
+/// specified level, returning the value.
+///
+/// Logs with the optional or default (module path of use) target, specified
+/// `Level`, optional `novalue` marker, optional prefix, optional or default
+/// (`"{:?}"`) value format string, and the expression. The expression is
+/// evaluated exactly once, regardless of if the logging level is enabled,
+/// with the elapsed wall-clock time measured via `std::time::Instant`, and
+/// its value is returned. The elapsed time is formatted via `Debug` of the
+/// `Duration`, with one fractional digit, e.g. `"work() → 3 (in 12.3ms)"`.
+///
+/// With the `novalue` marker, the value is not logged, and need not
+/// implement `Debug`, e.g. `"work() (in 12.3ms)"`.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+/// use log::Level;
+///
+/// fn work(n: u64) -> u64 { (1..=n).sum() }
+///
+/// # fn main() {
+/// let s = logv_time!(Level::Info, work(100));
+/// //      ^-- info level message: "work(100) → 5050 (in 1.2µs)"
+/// # assert_eq!(s, 5050);
+/// let v = debugv_time!(novalue, "collected", (0..10).collect::<Vec<_>>());
+/// //      ^-- debug level message: "collected (0..10).collect::<Vec<_>>() (in 3.4µs)"
+/// # assert_eq!(v.len(), 10);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! logv_time {
+    (target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_timev!(@val $target, $lvl, $($arg)+)
+    );
+    ($lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_timev!(@val module_path!(), $lvl, $($arg)+)
+    );
+}
+
+/// Log an expression, its value and time taken at the error level, returning
+/// its value.
+#[macro_export]
+macro_rules! errorv_time {
+    ($($arg:tt)+) => (
+        $crate::__tao_timev!(@target $crate::log::Level::Error, $($arg)+)
+    )
+}
+
+/// Log an expression, its value and time taken at the warn level, returning
+/// its value.
+#[macro_export]
+macro_rules! warnv_time {
+    ($($arg:tt)+) => (
+        $crate::__tao_timev!(@target $crate::log::Level::Warn, $($arg)+)
+    )
+}
+
+/// Log an expression, its value and time taken at the info level, returning
+/// its value.
+#[macro_export]
+macro_rules! infov_time {
+    ($($arg:tt)+) => (
+        $crate::__tao_timev!(@target $crate::log::Level::Info, $($arg)+)
+    )
+}
+
+/// Log an expression, its value and time taken at the debug level, returning
+/// its value.
+#[macro_export]
+macro_rules! debugv_time {
+    ($($arg:tt)+) => (
+        $crate::__tao_timev!(@target $crate::log::Level::Debug, $($arg)+)
+    )
+}
+
+/// Log an expression, its value and time taken at the trace level, returning
+/// its value.
+#[macro_export]
+macro_rules! tracev_time {
+    ($($arg:tt)+) => (
+        $crate::__tao_timev!(@target $crate::log::Level::Trace, $($arg)+)
     )
 }
+
+/// Log an expression only if its evaluation takes longer than a threshold,
+/// returning its value.
+///
+/// Logs with the optional or default (module path of use) target, optional
+/// `level:` (default: warn), the threshold `Duration`, optional prefix, and
+/// the expression. The expression is evaluated exactly once, and its value
+/// is returned. If the logging level is enabled, the elapsed wall-clock time
+/// of the evaluation is measured via `std::time::Instant`, and if it exceeds
+/// the threshold, the expression and elapsed time are logged, e.g. `"query()
+/// (in 12.3ms > 10ms)"`. The value itself is not logged, and need not
+/// implement `Debug`. If the level is not enabled, the time isn't measured.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use tao_log::*;
+/// use log::Level;
+///
+/// fn query(n: u64) -> u64 { (1..=n).sum() }
+///
+/// # fn main() {
+/// let limit = Duration::from_millis(10);
+/// let s = slowv!(limit, query(100));
+/// //      ^-- if slow, warn level message: "query(100) (in 12.3ms > 10ms)"
+/// # assert_eq!(s, 5050);
+/// slowv!(target: "db", level: Level::Info, limit, "slow", query(1));
+/// //      ^-- if slow, info level message: "slow query(1) (in 12.3ms > 10ms)"
+/// # }
+/// ```
+#[macro_export]
+macro_rules! slowv {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_slowv!(@level $target, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_slowv!(@level module_path!(), $($arg)+)
+    );
+}
+
+/// Log entry to a named scope, returning a guard which logs exit and the
+/// elapsed time when dropped.
+///
+/// Logs with the optional or default (module path of use) target, optional
+/// `Level` (default: debug) and scope name. The returned [`Scope`] guard
+/// logs entry when created, e.g. `"→ handle"`, and exit when dropped, e.g.
+/// `"← handle (in 12.3ms)"`, including when unwinding from a panic, flagged
+/// as `"panicked"`. Nested scopes of the same thread are indented, such that
+/// the log reads as a call tree.
+///
+/// Note that the guard must be bound to a named variable: With `let _ =
+/// scopev!(…)`, it is dropped immediately.
+///
+/// [`Scope`]: scope/struct.Scope.html
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+/// use log::Level;
+///
+/// fn parse() {
+///     let _s = scopev!(target: "req", Level::Info, "parse");
+///     // ...
+/// }
+///
+/// # fn main() {
+/// let _s = scopev!("handle");
+/// parse();
+/// // ^-- debug log: → handle
+/// // ^-- info log:    → parse
+/// // ^-- info log:    ← parse (in 1.2µs)
+/// drop(_s);
+/// // ^-- debug log: ← handle (in 5.6µs)
+/// # }
+/// ```
+#[macro_export]
+macro_rules! scopev {
+    (target: $target:expr, $lvl:expr, $name:expr $(,)?) => (
+        $crate::scope::Scope::new(
+            $target, $lvl, $name, module_path!(), file!(), line!()
+        )
+    );
+    (target: $target:expr, $name:expr $(,)?) => (
+        $crate::scopev!(target: $target, $crate::log::Level::Debug, $name)
+    );
+    ($lvl:expr, $name:expr $(,)?) => (
+        $crate::scopev!(target: module_path!(), $lvl, $name)
+    );
+    ($name:expr $(,)?) => (
+        $crate::scopev!(target: module_path!(), $crate::log::Level::Debug, $name)
+    );
+}
+
+/// Log a boolean condition, its value and the values of its sub-expressions,
+/// at any specified level, returning the value.
+///
+/// Logs with the optional or default (module path of use) target, specified
+/// `Level`, optional prefix, and the condition. The condition is evaluated
+/// exactly once, regardless of if the logging level is enabled, and its value
+/// is returned, for use directly in an `if`.
+///
+/// If the level is enabled, each evaluated sub-expression of a condition
+/// composed of `&&`, `||` and comparisons is logged with its value, one per
+/// line in the same record and indented as a tree, with short circuit
+/// semantics preserved. The operands of comparisons are borrowed, and logged
+/// only if they implement `Debug`. This requires the `proc-macro` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+/// use log::Level;
+///
+/// let (v, limit, flag) = (vec![1, 2, 3], 2, true);
+/// if logv_check!(Level::Info, "trim", v.len() > limit && flag) {
+///     // ^-- info level message:
+///     //     trim v.len() > limit && flag → true
+///     //       v.len() > limit → true
+///     //         v.len() → 3
+///     //         limit → 2
+///     //       flag → true
+/// }
+/// ```
+#[cfg(feature = "proc-macro")]
+#[macro_export]
+macro_rules! logv_check {
+    (target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_checkv_impl!($crate, $lvl, target: $target, $($arg)+)
+    );
+    ($lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_checkv_impl!($crate, $lvl, $($arg)+)
+    );
+}
+
+/// Log a boolean condition and its sub-expressions at the error level,
+/// returning its value.
+#[cfg(feature = "proc-macro")]
+#[macro_export]
+macro_rules! errorv_check {
+    ($($arg:tt)+) => (
+        $crate::__tao_checkv_impl!($crate, $crate::log::Level::Error, $($arg)+)
+    );
+}
+
+/// Log a boolean condition and its sub-expressions at the warn level,
+/// returning its value.
+#[cfg(feature = "proc-macro")]
+#[macro_export]
+macro_rules! warnv_check {
+    ($($arg:tt)+) => (
+        $crate::__tao_checkv_impl!($crate, $crate::log::Level::Warn, $($arg)+)
+    );
+}
+
+/// Log a boolean condition and its sub-expressions at the info level,
+/// returning its value.
+#[cfg(feature = "proc-macro")]
+#[macro_export]
+macro_rules! infov_check {
+    ($($arg:tt)+) => (
+        $crate::__tao_checkv_impl!($crate, $crate::log::Level::Info, $($arg)+)
+    );
+}
+
+/// Log a boolean condition and its sub-expressions at the debug level,
+/// returning its value.
+#[cfg(feature = "proc-macro")]
+#[macro_export]
+macro_rules! debugv_check {
+    ($($arg:tt)+) => (
+        $crate::__tao_checkv_impl!($crate, $crate::log::Level::Debug, $($arg)+)
+    );
+}
+
+/// Log a boolean condition and its sub-expressions at the trace level,
+/// returning its value.
+#[cfg(feature = "proc-macro")]
+#[macro_export]
+macro_rules! tracev_check {
+    ($($arg:tt)+) => (
+        $crate::__tao_checkv_impl!($crate, $crate::log::Level::Trace, $($arg)+)
+    );
+}
+
+/// Log a message at any specified level, only the first time this location
+/// of use (callsite) is reached with the level enabled.
+///
+/// This takes the same arguments as `log!`. State is kept in a per-callsite
+/// static flag, without any locking.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+/// use log::Level;
+///
+/// # fn main() {
+/// for i in 0..3 {
+///     log_once!(Level::Warn, "deprecated config, iteration {}", i);
+///     // ^-- warn level message, once: "deprecated config, iteration 0"
+///     warn_once!(target: "config", "also once");
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! log_once {
+    (target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@fmt $target, $lvl, $($arg)+)
+    );
+    ($lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@fmt module_path!(), $lvl, $($arg)+)
+    );
+}
+
+/// Log a message at the error level, only the first time reached.
+#[macro_export]
+macro_rules! error_once {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@fmt $target, $crate::log::Level::Error, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_once!(@fmt module_path!(), $crate::log::Level::Error, $($arg)+)
+    );
+}
+
+/// Log a message at the warn level, only the first time reached.
+#[macro_export]
+macro_rules! warn_once {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@fmt $target, $crate::log::Level::Warn, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_once!(@fmt module_path!(), $crate::log::Level::Warn, $($arg)+)
+    );
+}
+
+/// Log a message at the info level, only the first time reached.
+#[macro_export]
+macro_rules! info_once {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@fmt $target, $crate::log::Level::Info, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_once!(@fmt module_path!(), $crate::log::Level::Info, $($arg)+)
+    );
+}
+
+/// Log a message at the debug level, only the first time reached.
+#[macro_export]
+macro_rules! debug_once {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@fmt $target, $crate::log::Level::Debug, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_once!(@fmt module_path!(), $crate::log::Level::Debug, $($arg)+)
+    );
+}
+
+/// Log a message at the trace level, only the first time reached.
+#[macro_export]
+macro_rules! trace_once {
+    (target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@fmt $target, $crate::log::Level::Trace, $($arg)+)
+    );
+    ($($arg:tt)+) => (
+        $crate::__tao_once!(@fmt module_path!(), $crate::log::Level::Trace, $($arg)+)
+    );
+}
+
+/// Log an expression and its value at any specified level, only the first
+/// time this location of use (callsite) is reached with the level enabled,
+/// returning its value.
+///
+/// This takes the same arguments as `logv!`. The expression(s) are always
+/// evaluated and returned, and are logged at most once. State is kept in a
+/// per-callsite static flag, without any locking.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+///
+/// # fn main() {
+/// let mut sum = 0;
+/// for i in 0..3 {
+///     sum += debugv_once!("first", i);
+///     // ^-- debug level message, once: "first i → 0"
+/// }
+/// # assert_eq!(sum, 3);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! logv_once {
+    (target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@v $lvl, target: $target, $($arg)+)
+    );
+    ($lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_once!(@v $lvl, $($arg)+)
+    );
+}
+
+/// Log an expression at the error level, only the first time reached,
+/// returning its value.
+#[macro_export]
+macro_rules! errorv_once {
+    ($($arg:tt)+) => ($crate::__tao_once!(@v $crate::log::Level::Error, $($arg)+))
+}
+
+/// Log an expression at the warn level, only the first time reached,
+/// returning its value.
+#[macro_export]
+macro_rules! warnv_once {
+    ($($arg:tt)+) => ($crate::__tao_once!(@v $crate::log::Level::Warn, $($arg)+))
+}
+
+/// Log an expression at the info level, only the first time reached,
+/// returning its value.
+#[macro_export]
+macro_rules! infov_once {
+    ($($arg:tt)+) => ($crate::__tao_once!(@v $crate::log::Level::Info, $($arg)+))
+}
+
+/// Log an expression at the debug level, only the first time reached,
+/// returning its value.
+#[macro_export]
+macro_rules! debugv_once {
+    ($($arg:tt)+) => ($crate::__tao_once!(@v $crate::log::Level::Debug, $($arg)+))
+}
+
+/// Log an expression at the trace level, only the first time reached,
+/// returning its value.
+#[macro_export]
+macro_rules! tracev_once {
+    ($($arg:tt)+) => ($crate::__tao_once!(@v $crate::log::Level::Trace, $($arg)+))
+}
+
+/// Log a message at any specified level, every `n`th time this location of
+/// use (callsite) is reached with the level enabled, starting with the first.
+///
+/// This takes `n` (a `usize`, where 0 is taken as 1) followed by the same
+/// arguments as `log!`. Records after the first are appended with the count
+/// of suppressed messages since the last, e.g. `"retrying (suppressed 9
+/// similar messages)"`. State is kept in a per-callsite static counter,
+/// without any locking.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+/// use log::Level;
+///
+/// # fn main() {
+/// for i in 0..25 {
+///     log_every_n!(10, Level::Info, "retrying {}", i);
+///     // ^-- info level messages: "retrying 0",
+///     //     "retrying 10 (suppressed 9 similar messages)",
+///     //     "retrying 20 (suppressed 9 similar messages)"
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! log_every_n {
+    ($n:expr, target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@fmt $n, $target, $lvl, $($arg)+)
+    );
+    ($n:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@fmt $n, module_path!(), $lvl, $($arg)+)
+    );
+}
+
+/// Log a message at the error level, every `n`th time reached.
+#[macro_export]
+macro_rules! error_every_n {
+    ($n:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, $target, $crate::log::Level::Error, $($arg)+
+        )
+    );
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, module_path!(), $crate::log::Level::Error, $($arg)+
+        )
+    );
+}
+
+/// Log a message at the warn level, every `n`th time reached.
+#[macro_export]
+macro_rules! warn_every_n {
+    ($n:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, $target, $crate::log::Level::Warn, $($arg)+
+        )
+    );
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, module_path!(), $crate::log::Level::Warn, $($arg)+
+        )
+    );
+}
+
+/// Log a message at the info level, every `n`th time reached.
+#[macro_export]
+macro_rules! info_every_n {
+    ($n:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, $target, $crate::log::Level::Info, $($arg)+
+        )
+    );
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, module_path!(), $crate::log::Level::Info, $($arg)+
+        )
+    );
+}
+
+/// Log a message at the debug level, every `n`th time reached.
+#[macro_export]
+macro_rules! debug_every_n {
+    ($n:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, $target, $crate::log::Level::Debug, $($arg)+
+        )
+    );
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, module_path!(), $crate::log::Level::Debug, $($arg)+
+        )
+    );
+}
+
+/// Log a message at the trace level, every `n`th time reached.
+#[macro_export]
+macro_rules! trace_every_n {
+    ($n:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, $target, $crate::log::Level::Trace, $($arg)+
+        )
+    );
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(
+            @fmt $n, module_path!(), $crate::log::Level::Trace, $($arg)+
+        )
+    );
+}
+
+/// Log an expression and its value at any specified level, every `n`th time
+/// this location of use (callsite) is reached with the level enabled,
+/// starting with the first, returning its value.
+///
+/// This takes `n` (a `usize`, where 0 is taken as 1) followed by the same
+/// arguments as `logv!`. The expression(s) are always evaluated and
+/// returned. As with `log_every_n!`, records after the first are appended
+/// with the count of suppressed messages since the last, also as a
+/// `suppressed` key-value pair with the `kv` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+///
+/// # fn main() {
+/// for i in 0..25 {
+///     debugv_every_n!(10, i * 2);
+///     // ^-- debug level messages: "i * 2 → 0",
+///     //     "i * 2 → 20 (suppressed 9 similar messages)",
+///     //     "i * 2 → 40 (suppressed 9 similar messages)"
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! logv_every_n {
+    ($n:expr, target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@v $n, $lvl, target: $target, $($arg)+)
+    );
+    ($n:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@v $n, $lvl, $($arg)+)
+    );
+}
+
+/// Log an expression at the error level, every `n`th time reached, returning
+/// its value.
+#[macro_export]
+macro_rules! errorv_every_n {
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@v $n, $crate::log::Level::Error, $($arg)+)
+    )
+}
+
+/// Log an expression at the warn level, every `n`th time reached, returning
+/// its value.
+#[macro_export]
+macro_rules! warnv_every_n {
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@v $n, $crate::log::Level::Warn, $($arg)+)
+    )
+}
+
+/// Log an expression at the info level, every `n`th time reached, returning
+/// its value.
+#[macro_export]
+macro_rules! infov_every_n {
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@v $n, $crate::log::Level::Info, $($arg)+)
+    )
+}
+
+/// Log an expression at the debug level, every `n`th time reached, returning
+/// its value.
+#[macro_export]
+macro_rules! debugv_every_n {
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@v $n, $crate::log::Level::Debug, $($arg)+)
+    )
+}
+
+/// Log an expression at the trace level, every `n`th time reached, returning
+/// its value.
+#[macro_export]
+macro_rules! tracev_every_n {
+    ($n:expr, $($arg:tt)+) => (
+        $crate::__tao_every_n!(@v $n, $crate::log::Level::Trace, $($arg)+)
+    )
+}
+
+/// Log a message at any specified level, at most once per time interval for
+/// this location of use (callsite).
+///
+/// This takes the minimum interval `Duration` between records, followed by
+/// the same arguments as `log!`. The next record emitted after any are
+/// suppressed is appended with their count, e.g. `"reconnecting (suppressed
+/// 12 similar messages)"`. State is kept in per-callsite statics.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use tao_log::*;
+/// use log::Level;
+///
+/// # fn main() {
+/// for i in 0..100 {
+///     log_throttled!(Duration::from_secs(10), Level::Error, "reconnect {}", i);
+///     // ^-- error level message: "reconnect 0", then at most once per 10s,
+///     //     e.g. "reconnect 57 (suppressed 56 similar messages)"
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! log_throttled {
+    ($iv:expr, target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(@fmt $iv, $target, $lvl, $($arg)+)
+    );
+    ($iv:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(@fmt $iv, module_path!(), $lvl, $($arg)+)
+    );
+}
+
+/// Log a message at the error level, at most once per time interval.
+#[macro_export]
+macro_rules! error_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, $target, $crate::log::Level::Error, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, module_path!(), $crate::log::Level::Error, $($arg)+
+        )
+    );
+}
+
+/// Log a message at the warn level, at most once per time interval.
+#[macro_export]
+macro_rules! warn_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, $target, $crate::log::Level::Warn, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, module_path!(), $crate::log::Level::Warn, $($arg)+
+        )
+    );
+}
+
+/// Log a message at the info level, at most once per time interval.
+#[macro_export]
+macro_rules! info_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, $target, $crate::log::Level::Info, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, module_path!(), $crate::log::Level::Info, $($arg)+
+        )
+    );
+}
+
+/// Log a message at the debug level, at most once per time interval.
+#[macro_export]
+macro_rules! debug_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, $target, $crate::log::Level::Debug, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, module_path!(), $crate::log::Level::Debug, $($arg)+
+        )
+    );
+}
+
+/// Log a message at the trace level, at most once per time interval.
+#[macro_export]
+macro_rules! trace_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, $target, $crate::log::Level::Trace, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @fmt $iv, module_path!(), $crate::log::Level::Trace, $($arg)+
+        )
+    );
+}
+
+/// Log an expression and its value at any specified level, at most once per
+/// time interval for this location of use (callsite), returning its value.
+///
+/// This takes the minimum interval `Duration` between records, followed by
+/// the same arguments as `logv!`. The expression(s) are always evaluated and
+/// returned. When a record is emitted after any are suppressed, it is
+/// appended with their count, e.g. `"i → 57 (suppressed 12 similar
+/// messages)"`, also as a `suppressed` key-value pair with the `kv` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use tao_log::*;
+///
+/// # fn main() {
+/// let mut sum = 0;
+/// for i in 0..100 {
+///     sum += warnv_throttled!(Duration::from_secs(10), i);
+///     // ^-- warn level message: "i → 0", then at most once per 10s
+/// }
+/// # assert_eq!(sum, 4950);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! logv_throttled {
+    ($iv:expr, target: $target:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(@v $iv, $target, $lvl, $($arg)+)
+    );
+    ($iv:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(@v $iv, module_path!(), $lvl, $($arg)+)
+    );
+}
+
+/// Log an expression at the error level, at most once per time interval,
+/// returning its value.
+#[macro_export]
+macro_rules! errorv_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, $target, $crate::log::Level::Error, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, module_path!(), $crate::log::Level::Error, $($arg)+
+        )
+    );
+}
+
+/// Log an expression at the warn level, at most once per time interval,
+/// returning its value.
+#[macro_export]
+macro_rules! warnv_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, $target, $crate::log::Level::Warn, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, module_path!(), $crate::log::Level::Warn, $($arg)+
+        )
+    );
+}
+
+/// Log an expression at the info level, at most once per time interval,
+/// returning its value.
+#[macro_export]
+macro_rules! infov_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, $target, $crate::log::Level::Info, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, module_path!(), $crate::log::Level::Info, $($arg)+
+        )
+    );
+}
+
+/// Log an expression at the debug level, at most once per time interval,
+/// returning its value.
+#[macro_export]
+macro_rules! debugv_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, $target, $crate::log::Level::Debug, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, module_path!(), $crate::log::Level::Debug, $($arg)+
+        )
+    );
+}
+
+/// Log an expression at the trace level, at most once per time interval,
+/// returning its value.
+#[macro_export]
+macro_rules! tracev_throttled {
+    ($iv:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, $target, $crate::log::Level::Trace, $($arg)+
+        )
+    );
+    ($iv:expr, $($arg:tt)+) => (
+        $crate::__tao_throttled!(
+            @v $iv, module_path!(), $crate::log::Level::Trace, $($arg)+
+        )
+    );
+}
+
+/// Assert that a record matching all given criteria was captured on the
+/// current thread (`testing` feature).
+///
+/// Criteria are given as `name = value` pairs, of: `level` (a `Level`
+/// variant name or expression), `target` and `msg`, the latter two compared
+/// for equality. On failure, this panics with all captured records. See the
+/// `testing` module.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+///
+/// let _cap = tao_log::testing::capture();
+/// let i = 32;
+/// debug!(target: "special", "i = {}", i);
+/// assert_logged!(level = Debug, target = "special", msg = "i = 32");
+/// assert_logged!(msg = "i = 32");
+/// assert_not_logged!(level = Info);
+/// assert_logged_matches!(msg = "i = *");
+/// ```
+#[cfg(feature = "testing")]
+#[macro_export]
+macro_rules! assert_logged {
+    ($($arg:tt)+) => (
+        $crate::testing::__assert_logged(
+            &$crate::__tao_matcher!($crate::testing::Matcher::new(); $($arg)+),
+            true,
+            concat!("assert_logged!(", stringify!($($arg)+), ")")
+        )
+    );
+}
+
+/// Assert that a record matching all given criteria, with patterns for the
+/// target and message, was captured on the current thread (`testing`
+/// feature).
+///
+/// This is as per `assert_logged!`, but `target` and `msg` are patterns,
+/// where `*` matches any sequence of characters and `?` any single
+/// character, matched against the entire string. For example, `msg =
+/// "*timeout*"` matches any message containing `timeout`.
+#[cfg(feature = "testing")]
+#[macro_export]
+macro_rules! assert_logged_matches {
+    ($($arg:tt)+) => (
+        $crate::testing::__assert_logged(
+            &$crate::__tao_matcher!(
+                $crate::testing::Matcher::new().glob(); $($arg)+
+            ),
+            true,
+            concat!("assert_logged_matches!(", stringify!($($arg)+), ")")
+        )
+    );
+}
+
+/// Assert that no record matching all given criteria was captured on the
+/// current thread (`testing` feature).
+///
+/// Criteria are as per `assert_logged!`.
+#[cfg(feature = "testing")]
+#[macro_export]
+macro_rules! assert_not_logged {
+    ($($arg:tt)+) => (
+        $crate::testing::__assert_logged(
+            &$crate::__tao_matcher!($crate::testing::Matcher::new(); $($arg)+),
+            false,
+            concat!("assert_not_logged!(", stringify!($($arg)+), ")")
+        )
+    );
+}
+
+/// Assert that records matching each of the given parenthesized criteria
+/// were captured on the current thread, in the given order (`testing`
+/// feature).
+///
+/// Each criteria is as per `assert_logged!` and must match a distinct
+/// record, but other records may be interleaved.
+///
+/// # Examples
+///
+/// ```rust
+/// use tao_log::*;
+///
+/// let _cap = tao_log::testing::capture();
+/// info!("connecting");
+/// debug!("detail");
+/// warn!("retrying");
+/// assert_logged_in_order!((msg = "connecting"), (level = Warn, msg = "retrying"));
+/// ```
+#[cfg(feature = "testing")]
+#[macro_export]
+macro_rules! assert_logged_in_order {
+    ($(($($arg:tt)+)),+ $(,)?) => (
+        $crate::testing::__assert_logged_in_order(
+            &[$($crate::__tao_matcher!($crate::testing::Matcher::new(); $($arg)+)),+],
+            concat!("assert_logged_in_order!(", stringify!($(($($arg)+)),+), ")")
+        )
+    );
+}
+
+// Helper macro for `fatal!`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_fatal {
+    ($target:expr, $exit:expr, $($arg:tt)+) => (
+        match format_args!($($arg)+) {
+            args => {
+                $crate::error!(target: $target, "{}", args);
+                $crate::log::logger().flush();
+                $crate::__private::terminate($exit);
+                $crate::fatal::install_hook();
+                panic!($crate::fatal::FatalError::new(
+                    args,
+                    $target,
+                    Some(module_path!()),
+                    Some(file!()),
+                    Some(line!())
+                ));
+            }
+        }
+    );
+}
+
+// Helper macro for `fatal_assert!`, logging only the condition.
+#[cfg(not(feature = "proc-macro"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_fatal_assert {
+    ($tgt:expr, $cond:expr $(,)?) => (
+        if !$cond {
+            $crate::__tao_fatal!(
+                $tgt, None, "assertion failed: {}", stringify!($cond)
+            )
+        }
+    );
+    ($tgt:expr, $cond:expr, $($arg:tt)+) => (
+        if !$cond {
+            $crate::__tao_fatal!(
+                $tgt, None, "assertion failed: {}: {}",
+                stringify!($cond), format_args!($($arg)+)
+            )
+        }
+    );
+}
+
+// With the proc-macro feature, `fatal_assert!` is instead implemented by the
+// tao-log-macros crate, which is passed our `$crate` for its output.
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_fatal_assert {
+    ($($arg:tt)+) => (
+        $crate::__tao_assert_impl!($crate, $($arg)+)
+    );
+}
+
+// Helper macro for `fatal_assert_eq!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_fatal_assert_eq {
+    ($tgt:expr, $left:expr, $right:expr $(,)?) => (
+        match (&$left, &$right) {
+            (l, r) => if !(*l == *r) {
+                $crate::__tao_fatal!(
+                    $tgt, None,
+                    concat!(
+                        "assertion failed: {} == {}",
+                        "\n  {} ", $crate::__tao_arrow!(), " {:?}",
+                        "\n  {} ", $crate::__tao_arrow!(), " {:?}"
+                    ),
+                    stringify!($left), stringify!($right),
+                    stringify!($left), l, stringify!($right), r
+                )
+            }
+        }
+    );
+    ($tgt:expr, $left:expr, $right:expr, $($arg:tt)+) => (
+        match (&$left, &$right) {
+            (l, r) => if !(*l == *r) {
+                $crate::__tao_fatal!(
+                    $tgt, None,
+                    concat!(
+                        "assertion failed: {} == {}: {}",
+                        "\n  {} ", $crate::__tao_arrow!(), " {:?}",
+                        "\n  {} ", $crate::__tao_arrow!(), " {:?}"
+                    ),
+                    stringify!($left), stringify!($right),
+                    format_args!($($arg)+),
+                    stringify!($left), l, stringify!($right), r
+                )
+            }
+        }
+    );
+}
+
+// Helper macro for the -v macros, handling the optional target. Note: The
+// required level parameter is first here for convenience of internal use with
+// variable-args. The internal `@suppressed <expr>` form is passed on as
+// `[<expr>]` (otherwise `[]`), see `__tao_v_suppressed!`.
+#[cfg(not(feature = "proc-macro"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_logv {
+    ($lvl:expr, @suppressed $sup:expr, target: $tgt:expr, $($arg:tt)+) => (
+        $crate::__tao_v_args!($tgt, $lvl, [$sup], $($arg)+)
+    );
+    ($lvl:expr, @suppressed $sup:expr, $($arg:tt)+) => (
+        $crate::__tao_v_args!(module_path!(), $lvl, [$sup], $($arg)+)
+    );
+    ($lvl:expr, target: $tgt:expr, $($arg:tt)+) => (
+        $crate::__tao_v_args!($tgt, $lvl, [], $($arg)+)
+    );
+    ($lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_v_args!(module_path!(), $lvl, [], $($arg)+)
+    );
+}
+
+// With the proc-macro feature, the -v macros are instead implemented by the
+// tao-log-macros crate, which is passed our `$crate` for its output.
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_logv {
+    ($($arg:tt)+) => (
+        $crate::__tao_logv_impl!($crate, $($arg)+)
+    );
+}
+
+// Inner helper macro for __tao_logv, handling the permutations of optional
+// prefix and value format parameters, for one or more expressions. A leading
+// literal is taken as the prefix, or a leading `prefix = <expr>` as a runtime
+// prefix, passed on as `[= <expr>]`. Each expression may be named, as
+// `<name> = <expr>`, with the name as its label instead of the stringified
+// expression, so multiple expressions are passed on as tokens. Leading `-`
+// arms are needed to avoid the `literal` fragment failing hard on expressions
+// like `-x`. The `$sup` count of suppressed messages, `[]` or `[<expr>]`, is
+// passed through to `__tao_v_suppressed!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_v_args {
+    ($tgt:expr, $lvl:expr, $sup:tt, template = $tpl:expr, $($rest:tt)+) => (
+        compile_error!(
+            "the -v `template` argument requires the tao-log `proc-macro` feature"
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, template = $tpl:expr $(,)?) => (
+        compile_error!(
+            "expected at least one expression to log after the template"
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, prefix = $pre:expr $(,)?) => (
+        compile_error!(
+            "expected at least one expression to log after the prefix"
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     prefix = $pre:expr, $name:ident = $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup,
+            concat!("{} {} ", $crate::__tao_arrow!(), " {:?}"),
+            [= $pre], [], stringify!($name), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, prefix = $pre:expr, $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup,
+            concat!("{} {} ", $crate::__tao_arrow!(), " {:?}"),
+            [= $pre], [], stringify!($exp), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, prefix = $pre:expr, - $($exp:tt)+) => (
+        $crate::__tao_v_multi!(
+            $tgt, $lvl, $sup, "{} ", [= $pre], [], - $($exp)+
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, prefix = $pre:expr, $vfmt:literal,
+     $name:ident = $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup,
+            concat!("{} {} ", $crate::__tao_arrow!(), " ", $vfmt),
+            [= $pre], [$vfmt], stringify!($name), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     prefix = $pre:expr, $vfmt:literal, $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup,
+            concat!("{} {} ", $crate::__tao_arrow!(), " ", $vfmt),
+            [= $pre], [$vfmt], stringify!($exp), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     prefix = $pre:expr, $vfmt:literal, $($exp:tt)+) => (
+        $crate::__tao_v_multi!(
+            $tgt, $lvl, $sup, "{} ", [= $pre], [$vfmt], $($exp)+
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, prefix = $pre:expr, $($exp:tt)+) => (
+        $crate::__tao_v_multi!($tgt, $lvl, $sup, "{} ", [= $pre], [], $($exp)+)
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $name:ident = $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup, concat!("{} ", $crate::__tao_arrow!(), " {:?}"),
+            [], [], stringify!($name), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup, concat!("{} ", $crate::__tao_arrow!(), " {:?}"),
+            [], [], stringify!($exp), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, - $($exp:tt)+) => (
+        $crate::__tao_v_multi!($tgt, $lvl, $sup, "", [], [], - $($exp)+)
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     $pre:literal, $name:ident = $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup,
+            concat!($pre, " {} ", $crate::__tao_arrow!(), " {:?}"),
+            [$pre], [], stringify!($name), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $pre:literal, $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup,
+            concat!($pre, " {} ", $crate::__tao_arrow!(), " {:?}"),
+            [$pre], [], stringify!($exp), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $pre:literal, - $($exp:tt)+) => (
+        $crate::__tao_v_multi!(
+            $tgt, $lvl, $sup, concat!($pre, " "), [$pre], [], - $($exp)+
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $pre:literal, $vfmt:literal,
+     $name:ident = $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup,
+            concat!($pre, " {} ", $crate::__tao_arrow!(), " ", $vfmt),
+            [$pre], [$vfmt], stringify!($name), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     $pre:literal, $vfmt:literal, $exp:expr $(,)?) => (
+        $crate::__tao_v_eval!(
+            $tgt, $lvl, $sup,
+            concat!($pre, " {} ", $crate::__tao_arrow!(), " ", $vfmt),
+            [$pre], [$vfmt], stringify!($exp), $exp
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     $pre:literal, $vfmt:literal, $($exp:tt)+) => (
+        $crate::__tao_v_multi!(
+            $tgt, $lvl, $sup, concat!($pre, " "), [$pre], [$vfmt], $($exp)+
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $pre:literal, $($exp:tt)+) => (
+        $crate::__tao_v_multi!(
+            $tgt, $lvl, $sup, concat!($pre, " "), [$pre], [], $($exp)+
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $($exp:tt)+) => (
+        $crate::__tao_v_multi!($tgt, $lvl, $sup, "", [], [], $($exp)+)
+    );
+}
+
+// Inner helper macro for __tao_logv. Evaluates expression exactly once, moves
+// value and returns it, logged with the given label. The optional prefix and
+// value format are also passed separately, for key-value pairs. A runtime
+// prefix is evaluated once, only if the level is enabled, as the first format
+// argument. The expression is matched as a one-tuple so that a parenthesized
+// assignment, e.g. `(a = b)`, doesn't trip the `unused_parens` lint.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_v_eval {
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     $fmt:expr, [= $pre:expr], [], $lbl:expr, $exp:expr) => (
+        match ($exp,) {
+            (vt,) => {
+                let lvl = $lvl;
+                if $crate::__tao_enabled!(lvl) {
+                    let pre = &$pre;
+                    $crate::__tao_v_suppressed!(
+                        $tgt, lvl, $sup,
+                        [("prefix", display pre), ($lbl, debug vt)],
+                        $fmt, pre, $lbl, &vt
+                    );
+                }
+                vt
+            }
+        }
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $fmt:expr, [= $pre:expr], [$vfmt:expr],
+     $lbl:expr, $exp:expr) => (
+        match ($exp,) {
+            (vt,) => {
+                let lvl = $lvl;
+                if $crate::__tao_enabled!(lvl) {
+                    let pre = &$pre;
+                    $crate::__tao_v_suppressed!(
+                        $tgt, lvl, $sup,
+                        [
+                            ("prefix", display pre),
+                            ($lbl, display format_args!($vfmt, vt))
+                        ],
+                        $fmt, pre, $lbl, &vt
+                    );
+                }
+                vt
+            }
+        }
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     $fmt:expr, [$($pre:expr)?], [], $lbl:expr, $exp:expr) => (
+        match ($exp,) {
+            (vt,) => {
+                $crate::__tao_v_suppressed!(
+                    $tgt, $lvl, $sup,
+                    [$(("prefix", literal $pre),)? ($lbl, debug vt)],
+                    $fmt, $lbl, &vt
+                );
+                vt
+            }
+        }
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $fmt:expr, [$($pre:expr)?], [$vfmt:expr],
+     $lbl:expr, $exp:expr) => (
+        match ($exp,) {
+            (vt,) => {
+                $crate::__tao_v_suppressed!(
+                    $tgt, $lvl, $sup,
+                    [
+                        $(("prefix", literal $pre),)?
+                        ($lbl, display format_args!($vfmt, vt))
+                    ],
+                    $fmt, $lbl, &vt
+                );
+                vt
+            }
+        }
+    );
+}
+
+// Inner helper macro for __tao_logv with multiple expressions. Evaluates each
+// expression exactly once, in order, via nested matches (relying on macro
+// hygiene for distinct `vt` bindings), then logs a single record and returns
+// the tuple of values. The `@next` arms munch one, optionally named,
+// expression at a time, accumulating its label and value binding.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_v_multi {
+    (@next $tgt:expr, $lvl:expr, $sup:tt,
+     $fmt:expr, $sep:expr, [= $pre:expr], [],
+     $vf:expr, [$(($lbl:expr, $vt:ident))*],) => ({
+        let lvl = $lvl;
+        if $crate::__tao_enabled!(lvl) {
+            let pre = &$pre;
+            $crate::__tao_v_suppressed!(
+                $tgt, lvl, $sup,
+                [("prefix", display pre), $(($lbl, debug $vt)),*],
+                $fmt, pre, $($lbl, &$vt),*
+            );
+        }
+        ($($vt,)*)
+    });
+    (@next $tgt:expr, $lvl:expr, $sup:tt, $fmt:expr, $sep:expr, [= $pre:expr],
+     [$vfmt:expr], $vf:expr, [$(($lbl:expr, $vt:ident))*],) => ({
+        let lvl = $lvl;
+        if $crate::__tao_enabled!(lvl) {
+            let pre = &$pre;
+            $crate::__tao_v_suppressed!(
+                $tgt, lvl, $sup,
+                [
+                    ("prefix", display pre),
+                    $(($lbl, display format_args!($vfmt, $vt))),*
+                ],
+                $fmt, pre, $($lbl, &$vt),*
+            );
+        }
+        ($($vt,)*)
+    });
+    (@next $tgt:expr, $lvl:expr, $sup:tt,
+     $fmt:expr, $sep:expr, [$($pre:expr)?], [],
+     $vf:expr, [$(($lbl:expr, $vt:ident))*],) => ({
+        $crate::__tao_v_suppressed!(
+            $tgt, $lvl, $sup,
+            [$(("prefix", literal $pre),)? $(($lbl, debug $vt)),*],
+            $fmt, $($lbl, &$vt),*
+        );
+        ($($vt,)*)
+    });
+    (@next $tgt:expr, $lvl:expr, $sup:tt, $fmt:expr, $sep:expr, [$($pre:expr)?],
+     [$vfmt:expr], $vf:expr, [$(($lbl:expr, $vt:ident))*],) => ({
+        $crate::__tao_v_suppressed!(
+            $tgt, $lvl, $sup,
+            [
+                $(("prefix", literal $pre),)?
+                $(($lbl, display format_args!($vfmt, $vt))),*
+            ],
+            $fmt, $($lbl, &$vt),*
+        );
+        ($($vt,)*)
+    });
+    (@next $tgt:expr, $lvl:expr, $sup:tt,
+     $fmt:expr, $sep:expr, $pre:tt, $vfmt:tt,
+     $vf:expr, [$($done:tt)*], $name:ident = $exp:expr $(, $($rest:tt)*)?) => (
+        match ($exp,) {
+            (vt,) => $crate::__tao_v_multi!(
+                @next $tgt, $lvl, $sup,
+                concat!($fmt, $sep, "{} ", $crate::__tao_arrow!(), " ", $vf),
+                ", ", $pre, $vfmt, $vf,
+                [$($done)* (stringify!($name), vt)], $($($rest)*)?
+            )
+        }
+    );
+    (@next $tgt:expr, $lvl:expr, $sup:tt,
+     $fmt:expr, $sep:expr, $pre:tt, $vfmt:tt,
+     $vf:expr, [$($done:tt)*], $exp:expr $(, $($rest:tt)*)?) => (
+        match ($exp,) {
+            (vt,) => $crate::__tao_v_multi!(
+                @next $tgt, $lvl, $sup,
+                concat!($fmt, $sep, "{} ", $crate::__tao_arrow!(), " ", $vf),
+                ", ", $pre, $vfmt, $vf,
+                [$($done)* (stringify!($exp), vt)], $($($rest)*)?
+            )
+        }
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt, $pfmt:expr, $pre:tt, [], $($exp:tt)+) => (
+        $crate::__tao_v_multi!(
+            @next $tgt, $lvl, $sup, $pfmt, "", $pre, [], "{:?}", [], $($exp)+
+        )
+    );
+    ($tgt:expr, $lvl:expr, $sup:tt,
+     $pfmt:expr, $pre:tt, [$vfmt:expr], $($exp:tt)+) => (
+        $crate::__tao_v_multi!(
+            @next $tgt, $lvl, $sup, $pfmt, "", $pre, [$vfmt], $vfmt, [],
+            $($exp)+
+        )
+    );
+}
+
+// Helper macro for the logv_result macros, handling in turn the optional
+// target, `err:` level, `sources` marker and prefix, then logging with the
+// `Ok` or `Err` level.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_resultv {
+    (@target $ok:expr, target: $tgt:expr, $($arg:tt)+) => (
+        $crate::__tao_resultv!(@err $tgt, $ok, $($arg)+)
+    );
+    (@target $ok:expr, $($arg:tt)+) => (
+        $crate::__tao_resultv!(@err module_path!(), $ok, $($arg)+)
+    );
+    (@err $tgt:expr, $ok:expr, err: $err:expr, $($arg:tt)+) => (
+        $crate::__tao_resultv!(@src $tgt, $ok, $err, $($arg)+)
+    );
+    (@err $tgt:expr, $ok:expr, $($arg:tt)+) => (
+        $crate::__tao_resultv!(
+            @src $tgt, $ok, $crate::log::Level::Error, $($arg)+
+        )
+    );
+    (@src $tgt:expr, $ok:expr, $err:expr, sources, $($arg:tt)+) => (
+        $crate::__tao_resultv!(@pre $tgt, $ok, $err, [sources], $($arg)+)
+    );
+    (@src $tgt:expr, $ok:expr, $err:expr, $($arg:tt)+) => (
+        $crate::__tao_resultv!(@pre $tgt, $ok, $err, [], $($arg)+)
+    );
+    (@pre $tgt:expr, $ok:expr, $err:expr, [$($src:ident)?],
+     $pre:literal, $exp:expr $(,)?) => (
+        $crate::__tao_resultv!(
+            @log $tgt, $ok, $err, [$($src)?],
+            concat!($pre, " "), [("prefix", literal $pre),], $exp
+        )
+    );
+    (@pre $tgt:expr, $ok:expr, $err:expr, [$($src:ident)?],
+     $exp:expr $(,)?) => (
+        $crate::__tao_resultv!(@log $tgt, $ok, $err, [$($src)?], "", [], $exp)
+    );
+    (@log $tgt:expr, $ok:expr, $err:expr, [],
+     $pre:expr, [$($pkv:tt)*], $exp:expr) => (
+        match $exp {
+            vt => {
+                let lvl = match vt { Ok(_) => $ok, Err(_) => $err };
+                $crate::__tao_v_log!(
+                    $tgt, lvl, [$($pkv)* (stringify!($exp), debug vt)],
+                    concat!($pre, "{} ", $crate::__tao_arrow!(), " {:?}"),
+                    stringify!($exp), &vt
+                );
+                vt
+            }
+        }
+    );
+    (@log $tgt:expr, $ok:expr, $err:expr, [sources],
+     $pre:expr, [$($pkv:tt)*], $exp:expr) => (
+        match $exp {
+            vt => {
+                #[allow(unused_imports)]
+                use $crate::__private::Error;
+                let (lvl, srcs) = match vt {
+                    Ok(_) => ($ok, $crate::__private::Sources(None)),
+                    Err(ref e) => (
+                        $err, $crate::__private::Sources(e.source())
+                    ),
+                };
+                $crate::__tao_v_log!(
+                    $tgt, lvl,
+                    [$($pkv)* (stringify!($exp), debug vt),
+                     ("sources", display srcs)],
+                    concat!($pre, "{} ", $crate::__tao_arrow!(), " {:?}{}"),
+                    stringify!($exp), &vt, srcs
+                );
+                vt
+            }
+        }
+    );
+}
+
+// Helper macro for the -l macros, checking if enabled before evaluating and
+// logging via the -v macro implementation.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_logl {
+    ($tgt:expr, $lvl:expr, $($arg:tt)+) => ({
+        let lvl = $lvl;
+        if $crate::log_enabled!(target: $tgt, lvl) {
+            Some($crate::__tao_logv!(lvl, target: $tgt, $($arg)+))
+        } else {
+            None
+        }
+    });
+}
+
+// Helper macro for the error chain macros, handling in turn the optional
+// `multiline` and `backtrace` markers and prefix, then logging.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_chain {
+    (@ml $tgt:expr, $lvl:expr, multiline, $($arg:tt)+) => (
+        $crate::__tao_chain!(@bt $tgt, $lvl, true, $($arg)+)
+    );
+    (@ml $tgt:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@bt $tgt, $lvl, false, $($arg)+)
+    );
+    (@bt $tgt:expr, $lvl:expr, $ml:expr, backtrace, $($arg:tt)+) => (
+        $crate::__tao_chain!(@pre $tgt, $lvl, $ml, true, $($arg)+)
+    );
+    (@bt $tgt:expr, $lvl:expr, $ml:expr, $($arg:tt)+) => (
+        $crate::__tao_chain!(@pre $tgt, $lvl, $ml, false, $($arg)+)
+    );
+    (@pre $tgt:expr, $lvl:expr, $ml:expr, $bt:expr,
+     $pre:literal, $exp:expr $(,)?) => (
+        $crate::__tao_chain!(
+            @log $tgt, $lvl, $ml, $bt,
+            concat!($pre, ": "), [("prefix", literal $pre),], $exp
+        )
+    );
+    (@pre $tgt:expr, $lvl:expr, $ml:expr, $bt:expr, $exp:expr $(,)?) => (
+        $crate::__tao_chain!(@log $tgt, $lvl, $ml, $bt, "", [], $exp)
+    );
+    (@log $tgt:expr, $lvl:expr, $ml:expr, $bt:expr,
+     $pre:expr, [$($pkv:tt)*], $exp:expr) => (
+        match $exp {
+            vt => {
+                #[allow(unused_imports)]
+                use $crate::__private::Error;
+                let chain = $crate::__private::Chain::new(
+                    &vt, vt.source(), $ml, $bt
+                );
+                $crate::__tao_v_log!(
+                    $tgt, $lvl,
+                    [$($pkv)* ("error", display vt),
+                     ("sources",
+                      display $crate::__private::Sources(vt.source()))],
+                    concat!($pre, "{}"), chain
+                );
+                vt
+            }
+        }
+    );
+}
+
+// Helper macro for `tryv!`, handling in turn the optional `err:` level and
+// prefix, then logging any failure and applying `?`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_tryv {
+    (@err $tgt:expr, err: $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_tryv!(@pre $tgt, $lvl, $($arg)+)
+    );
+    (@err $tgt:expr, $($arg:tt)+) => (
+        $crate::__tao_tryv!(@pre $tgt, $crate::log::Level::Error, $($arg)+)
+    );
+    (@pre $tgt:expr, $lvl:expr, $pre:literal, $exp:expr $(,)?) => (
+        $crate::__tao_tryv!(
+            @log $tgt, $lvl, concat!($pre, " "), [("prefix", literal $pre),],
+            $exp
+        )
+    );
+    (@pre $tgt:expr, $lvl:expr, $exp:expr $(,)?) => (
+        $crate::__tao_tryv!(@log $tgt, $lvl, "", [], $exp)
+    );
+    (@log $tgt:expr, $lvl:expr, $pre:expr, [$($pkv:tt)*], $exp:expr) => (
+        match $exp {
+            vt => {
+                if $crate::__private::Fallible::failed(&vt) {
+                    let f = $crate::__private::Failure(&vt);
+                    $crate::__tao_v_log!(
+                        $tgt, $lvl, [$($pkv)* (stringify!($exp), debug f)],
+                        concat!($pre, "{} ", $crate::__tao_arrow!(), " {:?}"),
+                        stringify!($exp), &f
+                    );
+                }
+                vt?
+            }
+        }
+    );
+}
+
+// Helper macro for the logv_time macros, handling in turn the optional
+// target, `novalue` marker, prefix and value format, then timing and logging.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_timev {
+    (@target $lvl:expr, target: $tgt:expr, $($arg:tt)+) => (
+        $crate::__tao_timev!(@val $tgt, $lvl, $($arg)+)
+    );
+    (@target $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_timev!(@val module_path!(), $lvl, $($arg)+)
+    );
+    (@val $tgt:expr, $lvl:expr, novalue, $($arg:tt)+) => (
+        $crate::__tao_timev!(@pre $tgt, $lvl, [novalue], $($arg)+)
+    );
+    (@val $tgt:expr, $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_timev!(@pre $tgt, $lvl, [], $($arg)+)
+    );
+    (@pre $tgt:expr, $lvl:expr, [$($nv:ident)?],
+     $pre:literal, $vfmt:literal, $exp:expr $(,)?) => (
+        $crate::__tao_timev!(
+            @log $tgt, $lvl, [$($nv)?], concat!($pre, " "),
+            [("prefix", literal $pre),], [$vfmt], $exp
+        )
+    );
+    (@pre $tgt:expr, $lvl:expr, [$($nv:ident)?],
+     $pre:literal, $exp:expr $(,)?) => (
+        $crate::__tao_timev!(
+            @log $tgt, $lvl, [$($nv)?], concat!($pre, " "),
+            [("prefix", literal $pre),], [], $exp
+        )
+    );
+    (@pre $tgt:expr, $lvl:expr, [$($nv:ident)?], $exp:expr $(,)?) => (
+        $crate::__tao_timev!(@log $tgt, $lvl, [$($nv)?], "", [], [], $exp)
+    );
+    (@log $tgt:expr, $lvl:expr, [novalue], $pre:expr, [$($pkv:tt)*],
+     [$($vfmt:expr)?], $exp:expr) => ({
+        let start = ::std::time::Instant::now();
+        match $exp {
+            vt => {
+                let el = start.elapsed();
+                $crate::__tao_v_log!(
+                    $tgt, $lvl, [$($pkv)* ("elapsed", debug el)],
+                    concat!($pre, "{} (in {:.1?})"), stringify!($exp), el
+                );
+                vt
+            }
+        }
+    });
+    (@log $tgt:expr, $lvl:expr, [], $pre:expr, [$($pkv:tt)*],
+     [], $exp:expr) => ({
+        let start = ::std::time::Instant::now();
+        match $exp {
+            vt => {
+                let el = start.elapsed();
+                $crate::__tao_v_log!(
+                    $tgt, $lvl,
+                    [$($pkv)* (stringify!($exp), debug vt), ("elapsed", debug el)],
+                    concat!($pre, "{} ", $crate::__tao_arrow!(), " {:?} (in {:.1?})"),
+                    stringify!($exp), &vt, el
+                );
+                vt
+            }
+        }
+    });
+    (@log $tgt:expr, $lvl:expr, [], $pre:expr, [$($pkv:tt)*],
+     [$vfmt:expr], $exp:expr) => ({
+        let start = ::std::time::Instant::now();
+        match $exp {
+            vt => {
+                let el = start.elapsed();
+                $crate::__tao_v_log!(
+                    $tgt, $lvl,
+                    [$($pkv)* (stringify!($exp), display format_args!($vfmt, vt)),
+                     ("elapsed", debug el)],
+                    concat!(
+                        $pre, "{} ", $crate::__tao_arrow!(), " ", $vfmt,
+                        " (in {:.1?})"
+                    ),
+                    stringify!($exp), &vt, el
+                );
+                vt
+            }
+        }
+    });
+}
+
+// Helper macro for `slowv!`, handling in turn the optional `level:` and
+// prefix, then timing only if enabled, and logging if over the threshold.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_slowv {
+    (@level $tgt:expr, level: $lvl:expr, $($arg:tt)+) => (
+        $crate::__tao_slowv!(@pre $tgt, $lvl, $($arg)+)
+    );
+    (@level $tgt:expr, $($arg:tt)+) => (
+        $crate::__tao_slowv!(@pre $tgt, $crate::log::Level::Warn, $($arg)+)
+    );
+    (@pre $tgt:expr, $lvl:expr, $limit:expr, $pre:literal, $exp:expr $(,)?) => (
+        $crate::__tao_slowv!(
+            @log $tgt, $lvl, $limit, concat!($pre, " "),
+            [("prefix", literal $pre),], $exp
+        )
+    );
+    (@pre $tgt:expr, $lvl:expr, $limit:expr, $exp:expr $(,)?) => (
+        $crate::__tao_slowv!(@log $tgt, $lvl, $limit, "", [], $exp)
+    );
+    (@log $tgt:expr, $lvl:expr, $limit:expr, $pre:expr, [$($pkv:tt)*],
+     $exp:expr) => ({
+        let lvl = $lvl;
+        let limit: ::std::time::Duration = $limit;
+        let start = if lvl <= $crate::log::STATIC_MAX_LEVEL
+            && lvl <= $crate::log::max_level()
+        {
+            Some(::std::time::Instant::now())
+        } else {
+            None
+        };
+        match $exp {
+            vt => {
+                if let Some(start) = start {
+                    let el = start.elapsed();
+                    if el > limit {
+                        $crate::__tao_v_log!(
+                            $tgt, lvl,
+                            [$($pkv)* ("elapsed", debug el),
+                             ("threshold", debug limit)],
+                            concat!($pre, "{} (in {:.1?} > {:?})"),
+                            stringify!($exp), el, limit
+                        );
+                    }
+                }
+                vt
+            }
+        }
+    });
+}
+
+// Helper macro for the once macros, with `@fmt` for formatted messages and
+// `@v` for the -v macros. The latter still evaluates and returns the value(s)
+// when not logging, via `@suppressed` of `__tao_logv!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_once {
+    (@fmt $tgt:expr, $lvl:expr, $($arg:tt)+) => ({
+        static DONE: ::std::sync::atomic::AtomicBool =
+            ::std::sync::atomic::AtomicBool::new(false);
+        let lvl = $lvl;
+        if $crate::__tao_enabled!(lvl) && $crate::__tao_once!(@first DONE) {
+            $crate::log!(target: $tgt, lvl, $($arg)+);
+        }
+    });
+    (@v $lvl:expr, $($arg:tt)+) => ({
+        static DONE: ::std::sync::atomic::AtomicBool =
+            ::std::sync::atomic::AtomicBool::new(false);
+        let lvl = $lvl;
+        let sup = if $crate::__tao_enabled!(lvl)
+            && $crate::__tao_once!(@first DONE)
+        {
+            Some(0)
+        } else {
+            None
+        };
+        $crate::__tao_logv!(lvl, @suppressed sup, $($arg)+)
+    });
+    (@first $done:ident) => (
+        !$done.load(::std::sync::atomic::Ordering::Relaxed)
+            && !$done.swap(true, ::std::sync::atomic::Ordering::Relaxed)
+    );
+}
+
+// Helper macro for the every-n macros, with `@fmt` for formatted messages
+// and `@v` for the -v macros, as for `__tao_once!`. The `@hit` arm counts a
+// hit, returning the count of suppressed messages to log with, if any. An `n`
+// of 0 is taken as 1, rather than panicking on the remainder.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_every_n {
+    (@fmt $n:expr, $tgt:expr, $lvl:expr, $($arg:tt)+) => ({
+        static HITS: ::std::sync::atomic::AtomicUsize =
+            ::std::sync::atomic::AtomicUsize::new(0);
+        let lvl = $lvl;
+        if $crate::__tao_enabled!(lvl) {
+            match $crate::__tao_every_n!(@hit HITS, $n) {
+                Some(0) => $crate::log!(target: $tgt, lvl, $($arg)+),
+                Some(s) => $crate::log!(
+                    target: $tgt, lvl, "{} (suppressed {} similar messages)",
+                    format_args!($($arg)+), s
+                ),
+                None => {}
+            }
+        }
+    });
+    (@v $n:expr, $lvl:expr, $($arg:tt)+) => ({
+        static HITS: ::std::sync::atomic::AtomicUsize =
+            ::std::sync::atomic::AtomicUsize::new(0);
+        let lvl = $lvl;
+        let sup = if $crate::__tao_enabled!(lvl) {
+            $crate::__tao_every_n!(@hit HITS, $n)
+        } else {
+            None
+        };
+        $crate::__tao_logv!(lvl, @suppressed sup, $($arg)+)
+    });
+    (@hit $hits:ident, $n:expr) => ({
+        let n: usize = ::std::cmp::max($n, 1);
+        let hit = $hits.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed);
+        if hit == 0 {
+            Some(0)
+        } else if hit % n == 0 {
+            Some(n - 1)
+        } else {
+            None
+        }
+    });
+}
+
+// Helper macro for the throttled macros, with `@fmt` for formatted messages
+// and `@v` for the -v macros, as for `__tao_once!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_throttled {
+    (@fmt $iv:expr, $tgt:expr, $lvl:expr, $($arg:tt)+) => ({
+        static THROTTLE: $crate::__private::Throttle =
+            $crate::__private::Throttle::new();
+        let lvl = $lvl;
+        if $crate::__tao_enabled!(lvl) {
+            match THROTTLE.check($iv) {
+                Some(0) => $crate::log!(target: $tgt, lvl, $($arg)+),
+                Some(n) => $crate::log!(
+                    target: $tgt, lvl, "{} (suppressed {} similar messages)",
+                    format_args!($($arg)+), n
+                ),
+                None => {}
+            }
+        }
+    });
+    (@v $iv:expr, $tgt:expr, $lvl:expr, $($arg:tt)+) => ({
+        static THROTTLE: $crate::__private::Throttle =
+            $crate::__private::Throttle::new();
+        let lvl = $lvl;
+        let sup = if $crate::__tao_enabled!(lvl) {
+            THROTTLE.check($iv)
+        } else {
+            None
+        };
+        $crate::__tao_logv!(lvl, @suppressed sup, target: $tgt, $($arg)+)
+    });
+}
+
+// Helper macro for the testing assertion macros, building a `Matcher` from
+// the `name = value` criteria. A level may be given by variant name alone,
+// or otherwise as any expression, including a variable.
+#[cfg(feature = "testing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_matcher {
+    (@level Error) => ($crate::log::Level::Error);
+    (@level Warn) => ($crate::log::Level::Warn);
+    (@level Info) => ($crate::log::Level::Info);
+    (@level Debug) => ($crate::log::Level::Debug);
+    (@level Trace) => ($crate::log::Level::Trace);
+    (@level $lvl:ident) => ($lvl);
+    ($m:expr; $(,)?) => ($m);
+    ($m:expr; level = $lvl:ident $(, $($rest:tt)*)?) => (
+        $crate::__tao_matcher!(
+            $m.level($crate::__tao_matcher!(@level $lvl)); $($($rest)*)?
+        )
+    );
+    ($m:expr; $name:ident = $val:expr $(, $($rest:tt)*)?) => (
+        $crate::__tao_matcher!($m.$name($val); $($($rest)*)?)
+    );
+}
+
+// Helper macro expanding to the arrow literal separating an expression and its
+// value in messages, or with `@back` the arrow of a scope exit, which are ASCII
+// `->` and `<-` with the ascii-arrow feature.
+#[cfg(not(feature = "ascii-arrow"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_arrow {
+    () => ("→");
+    (@back) => ("←");
+}
+
+#[cfg(feature = "ascii-arrow")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_arrow {
+    () => ("->");
+    (@back) => ("<-");
+}
+
+// Helper macro returning true if the given level is enabled, statically and
+// via the current maximum level.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_enabled {
+    ($lvl:expr) => (
+        $lvl <= $crate::log::STATIC_MAX_LEVEL && $lvl <= $crate::log::max_level()
+    );
+}
+
+// Inner helper macro for the -v macros, logging the formatted message. The
+// key-value pairs, each a key and a `debug`, `display` or `literal` value,
+// are ignored without the kv feature.
+#[cfg(not(feature = "kv"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_v_log {
+    ($tgt:expr, $lvl:expr, [$($kv:tt)*], $($arg:tt)+) => (
+        $crate::log!(target: $tgt, $lvl, $($arg)+)
+    );
+}
+
+// With the kv feature, the record is built directly, to attach the key-value
+// pairs, since the keys aren't identifiers as required by `log!`.
+#[cfg(feature = "kv")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_v_log {
+    ($tgt:expr, $lvl:expr, [$(($k:expr, $kind:ident $v:expr)),* $(,)?],
+     $($arg:tt)+) => ({
+        let lvl = $lvl;
+        if lvl <= $crate::log::STATIC_MAX_LEVEL && lvl <= $crate::log::max_level() {
+            $crate::log::logger().log(
+                &$crate::log::Record::builder()
+                    .args(format_args!($($arg)+))
+                    .level(lvl)
+                    .target($tgt)
+                    .module_path(Some(module_path!()))
+                    .file(Some(file!()))
+                    .line(Some(line!()))
+                    .key_values(&(&[
+                        $(($k, $crate::__tao_v_kv!($kind $v))),*
+                    ] as &[(&str, $crate::log::kv::Value)]))
+                    .build()
+            );
+        }
+    });
+}
+
+// Inner helper macro for the -v macros, logging via `__tao_v_log!` as is, or
+// given a runtime `Option<usize>` count of suppressed messages, only if
+// `Some`, and for a non-zero count with it appended to the message and as a
+// `suppressed` key-value pair. This is used by the once, every-n and
+// throttled -v macros, which need only a single expansion of the arguments.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_v_suppressed {
+    ($tgt:expr, $lvl:expr, [], [$($kv:tt)*], $($arg:tt)+) => (
+        $crate::__tao_v_log!($tgt, $lvl, [$($kv)*], $($arg)+)
+    );
+    ($tgt:expr, $lvl:expr, [$sup:expr], [$($kv:tt)*], $fmt:expr
+     $(, $arg:expr)* $(,)?) => (
+        match $sup {
+            Some(0) => $crate::__tao_v_log!(
+                $tgt, $lvl, [$($kv)*], $fmt $(, $arg)*
+            ),
+            Some(n) => $crate::__tao_v_log!(
+                $tgt, $lvl, [$($kv)*, ("suppressed", debug n)],
+                concat!($fmt, " (suppressed {} similar messages)"),
+                $($arg,)* n
+            ),
+            None => {}
+        }
+    );
+}
+
+// Inner helper macro for __tao_v_log, converting to a kv `Value`. A `literal`
+// is a prefix literal, displayed as in the message, with any escaped braces.
+#[cfg(feature = "kv")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tao_v_kv {
+    (debug $v:expr) => ($crate::log::kv::Value::from_debug(&$v));
+    (display $v:expr) => ($crate::log::kv::Value::from_display(&$v));
+    (literal $v:expr) => (
+        $crate::log::kv::Value::from_display(&format_args!(concat!($v)))
+    );
+}
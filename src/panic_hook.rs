@@ -0,0 +1,144 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Panic hook logging all panics via the logger.
+//!
+//! Panics other than via `fatal!`, e.g. from `unwrap()` in dependencies, are
+//! otherwise only printed to stderr by the default panic hook. With the hook
+//! installed, each is also logged at the error level, as for example:
+//!
+//! ```text
+//! thread 'worker' panicked at 'called `Option::unwrap()` on a `None` value', src/main.rs:12:5
+//! ```
+//!
+//! ```rust
+//! use tao_log::panic_hook;
+//!
+//! panic_hook::install_hook();
+//! // or, configured:
+//! panic_hook::Hook::new().target("crash").backtrace(true).install();
+//! ```
+
+use std::any::Any;
+use std::panic;
+use std::thread;
+
+use log::{Level, Record};
+
+use crate::fatal::FatalError;
+
+/// Install a panic hook with default configuration.
+///
+/// This is equivalent to `Hook::new().install()`.
+pub fn install_hook() {
+    Hook::new().install()
+}
+
+/// Configuration of the panic hook, logging each panic at the error level,
+/// flushing the logger, then chaining to the previously installed hook.
+///
+/// Panics of `fatal!` are only chained, since their message was already
+/// logged.
+#[derive(Clone, Debug)]
+pub struct Hook {
+    target: String,
+    backtrace: bool,
+}
+
+impl Hook {
+    /// Construct with the default target `"panic"` and without backtrace.
+    pub fn new() -> Hook {
+        Hook { target: "panic".to_owned(), backtrace: false }
+    }
+
+    /// Set the target of the logged records.
+    pub fn target(mut self, target: &str) -> Hook {
+        self.target = target.to_owned();
+        self
+    }
+
+    /// Set whether to capture and log a backtrace of each panic, in the same
+    /// record. This requires rustc 1.65+ and is otherwise ignored.
+    pub fn backtrace(mut self, backtrace: bool) -> Hook {
+        self.backtrace = backtrace;
+        self
+    }
+
+    /// Install this as the panic hook, chaining to the prior hook.
+    pub fn install(self) {
+        let prior = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if FatalError::from_payload(info.payload()).is_none() {
+                self.log(info);
+            }
+            prior(info);
+        }));
+    }
+
+    #[allow(deprecated)] // PanicInfo, renamed PanicHookInfo in 1.81
+    fn log(&self, info: &panic::PanicInfo) {
+        if !__tao_enabled!(Level::Error) {
+            return;
+        }
+        let thread = thread::current();
+        let name = thread.name().unwrap_or("<unnamed>");
+        let msg = payload_msg(info.payload()).unwrap_or("Box<dyn Any>");
+        let (file, line) = match info.location() {
+            Some(l) => (Some(l.file()), Some(l.line())),
+            None => (None, None),
+        };
+        let at = match info.location() {
+            Some(l) => format!(", {}:{}:{}", l.file(), l.line(), l.column()),
+            None => String::new(),
+        };
+        let bt = self.capture_backtrace();
+        log::logger().log(
+            &Record::builder()
+                .args(format_args!(
+                    "thread '{}' panicked at '{}'{}{}", name, msg, at, bt
+                ))
+                .level(Level::Error)
+                .target(&self.target)
+                .file(file)
+                .line(line)
+                .build()
+        );
+        log::logger().flush();
+    }
+
+    #[cfg(tao_log_backtrace)]
+    #[allow(clippy::incompatible_msrv)] // per build.rs rustc version check
+    fn capture_backtrace(&self) -> String {
+        if self.backtrace {
+            format!(
+                "\nstack backtrace:\n{}",
+                std::backtrace::Backtrace::force_capture()
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    #[cfg(not(tao_log_backtrace))]
+    fn capture_backtrace(&self) -> String {
+        String::new()
+    }
+}
+
+impl Default for Hook {
+    fn default() -> Hook {
+        Hook::new()
+    }
+}
+
+// Return the message of a panic payload, if a string or `FatalError`.
+pub(crate) fn payload_msg(payload: &(dyn Any + Send)) -> Option<&str> {
+    if let Some(s) = payload.downcast_ref::<&'static str>() {
+        Some(s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        Some(s)
+    } else {
+        FatalError::from_payload(payload).map(FatalError::message)
+    }
+}
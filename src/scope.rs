@@ -0,0 +1,100 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Scope guard logging entry, exit and elapsed time.
+
+use std::cell::Cell;
+use std::fmt;
+use std::thread;
+use std::time::Instant;
+
+use log::{Level, Record};
+
+thread_local! {
+    // Count of enabled scopes of the current thread, for indentation.
+    static DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// Guard returned by `scopev!`, logging exit and elapsed time on drop.
+///
+/// Entry is logged on creation, e.g. `"→ handle"`, and exit on drop, e.g.
+/// `"← handle (in 12.3ms)"`, or `"← handle (in 12.3ms, panicked)"` if
+/// dropped while unwinding from a panic. Messages of nested scopes of the
+/// same thread are indented by two spaces per level, reading as a call tree.
+/// If the level is not enabled on creation, nothing is logged and no time is
+/// measured.
+#[must_use = "the scope ends (and exit is logged) when this is dropped"]
+pub struct Scope<'a> {
+    target: &'a str,
+    level: Level,
+    name: &'a str,
+    module_path: &'static str,
+    file: &'static str,
+    line: u32,
+    start: Option<Instant>,
+}
+
+impl<'a> Scope<'a> {
+    // Constructor for `scopev!`, logging entry if enabled.
+    #[doc(hidden)]
+    pub fn new(
+        target: &'a str,
+        level: Level,
+        name: &'a str,
+        module_path: &'static str,
+        file: &'static str,
+        line: u32)
+        -> Scope<'a>
+    {
+        let enabled = level <= log::STATIC_MAX_LEVEL
+            && level <= log::max_level();
+        let mut scope = Scope {
+            target, level, name, module_path, file, line, start: None
+        };
+        if enabled {
+            let depth = DEPTH.with(|d| {
+                let depth = d.get();
+                d.set(depth + 1);
+                depth
+            });
+            scope.log(format_args!(
+                concat!("{:w$}", __tao_arrow!(), " {}"), "", name, w = depth * 2
+            ));
+            scope.start = Some(Instant::now());
+        }
+        scope
+    }
+
+    fn log(&self, args: fmt::Arguments) {
+        log::logger().log(
+            &Record::builder()
+                .args(args)
+                .level(self.level)
+                .target(self.target)
+                .module_path(Some(self.module_path))
+                .file(Some(self.file))
+                .line(Some(self.line))
+                .build()
+        );
+    }
+}
+
+impl<'a> Drop for Scope<'a> {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            let el = start.elapsed();
+            let depth = DEPTH.with(|d| {
+                let depth = d.get().saturating_sub(1);
+                d.set(depth);
+                depth
+            });
+            let panicked = if thread::panicking() { ", panicked" } else { "" };
+            self.log(format_args!(
+                concat!("{:w$}", __tao_arrow!(@back), " {} (in {:.1?}{})"),
+                "", self.name, el, panicked,
+                w = depth * 2
+            ));
+        }
+    }
+}
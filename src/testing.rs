@@ -0,0 +1,437 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Capture of log records for tests (`testing` feature).
+//!
+//! A capture logger is installed as the global logger on first use, with a
+//! maximum level of trace. Records are captured per thread: Each test calls
+//! [`capture`] to start capturing the records logged on its own thread,
+//! until the returned [`Capture`] handle is dropped. Thus tests run in
+//! parallel by `cargo test` don't interfere with each other. Records logged
+//! on threads without a capture are discarded.
+//!
+//! ```rust
+//! use tao_log::*;
+//! use tao_log::testing;
+//!
+//! let cap = testing::capture();
+//! let i = 32;
+//! debug!("i = {}", i);
+//! let rec = cap.last().unwrap();
+//! assert_eq!(rec.level, log::Level::Debug);
+//! assert_eq!(rec.msg, "i = 32");
+//! ```
+//!
+//! The `assert_logged!`, `assert_logged_matches!`, `assert_not_logged!` and
+//! `assert_logged_in_order!` macros assert on the records captured on the
+//! current thread, printing all of these on failure.
+//!
+//! With the `proc-macro` feature, the [`test`] attribute replaces `#[test]`,
+//! capturing for the duration of the test and printing the captured records
+//! only if the test fails. The test function may take a `&Capture` argument,
+//! and a `check` function may assert on the records logged prior to an
+//! expected panic of a `should_panic` test:
+//!
+//! ```rust,ignore
+//! use tao_log::*;
+//! use tao_log::testing::{self, Capture};
+//!
+//! #[testing::test]
+//! fn logs_value(cap: &Capture) {
+//!     debugv!(1 + 1);
+//!     assert_eq!(cap.last().unwrap().msg, "1 + 1 → 2");
+//! }
+//!
+//! #[testing::test(check = "logged_fatal")]
+//! #[should_panic(expected = "core breach")]
+//! fn fatal_logged() {
+//!     fatal!("core breach");
+//! }
+//!
+//! fn logged_fatal(_cap: &Capture) {
+//!     assert_logged!(level = Error, msg = "core breach");
+//! }
+//! ```
+//!
+//! This isn't exported from the crate root, where it would shadow the
+//! built-in `#[test]` for the usual `use tao_log::*` glob import.
+//!
+//! [`capture`]: fn.capture.html
+//! [`Capture`]: struct.Capture.html
+//! [`test`]: attr.test.html
+
+use std::cell::RefCell;
+use std::fmt;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard, Once};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::panic_hook::payload_msg;
+
+#[cfg(feature = "proc-macro")]
+pub use tao_log_macros::test;
+
+/// A captured log record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Captured {
+    pub level: Level,
+    pub target: String,
+    /// The formatted message.
+    pub msg: String,
+    pub module_path: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    /// Key-value pairs, as strings via `Display` of the values. These are
+    /// only captured with the `kv` feature.
+    pub kvs: Vec<(String, String)>,
+}
+
+impl fmt::Display for Captured {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:5} {}: {}", self.level, self.target, self.msg)?;
+        for (k, v) in &self.kvs {
+            write!(f, " {}={}", k, v)?;
+        }
+        Ok(())
+    }
+}
+
+struct Buffer {
+    level: LevelFilter,
+    records: Vec<Captured>,
+}
+
+thread_local! {
+    // The current capture buffer of the thread, if any.
+    static CURRENT: RefCell<Option<Arc<Mutex<Buffer>>>> = RefCell::new(None);
+}
+
+/// Handle to the captured records of the current thread.
+///
+/// Capturing ends when this is dropped, restoring any prior capture of the
+/// thread.
+#[must_use = "capturing ends when this is dropped"]
+pub struct Capture {
+    buffer: Arc<Mutex<Buffer>>,
+    prior: Option<Arc<Mutex<Buffer>>>,
+}
+
+/// Start capturing all records logged on the current thread.
+///
+/// This installs the capture logger on first use, and panics if a different
+/// logger was already installed.
+pub fn capture() -> Capture {
+    capture_level(LevelFilter::Trace)
+}
+
+/// Start capturing records up to the given level, logged on the current
+/// thread.
+///
+/// Note that the global maximum level remains at trace, such that the _-v_
+/// macros (for example) will still format and attempt to log records above
+/// the given level, which are then discarded.
+pub fn capture_level(level: LevelFilter) -> Capture {
+    install();
+    let buffer = Arc::new(Mutex::new(Buffer { level, records: Vec::new() }));
+    let prior = CURRENT.with(|c| c.replace(Some(buffer.clone())));
+    Capture { buffer, prior }
+}
+
+impl Capture {
+    /// Return a copy of all records captured so far.
+    pub fn records(&self) -> Vec<Captured> {
+        self.lock().records.clone()
+    }
+
+    /// Return all records captured so far, and clear them.
+    pub fn take(&self) -> Vec<Captured> {
+        mem::replace(&mut self.lock().records, Vec::new())
+    }
+
+    /// Return the last record captured, if any, and clear all records.
+    pub fn last(&self) -> Option<Captured> {
+        self.take().pop()
+    }
+
+    /// Clear all records captured so far.
+    pub fn clear(&self) {
+        self.lock().records.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Buffer> {
+        // A panic while holding the lock doesn't invalidate the records
+        self.buffer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        let prior = self.prior.take();
+        // Ignore if thread-local storage is already destroyed
+        let _ = CURRENT.try_with(|c| c.replace(prior));
+    }
+}
+
+/// Criteria for matching captured records, as used by the assertion macros.
+///
+/// Each criterion set must match. With `glob`, the target and message
+/// criteria are patterns, where `*` matches any sequence of characters and
+/// `?` any single character, matched against the entire string. Otherwise
+/// these must be equal.
+#[derive(Clone, Debug, Default)]
+pub struct Matcher {
+    glob: bool,
+    level: Option<Level>,
+    target: Option<String>,
+    msg: Option<String>,
+}
+
+impl Matcher {
+    pub fn new() -> Matcher {
+        Matcher::default()
+    }
+
+    /// Match the target and message as patterns.
+    pub fn glob(mut self) -> Matcher {
+        self.glob = true;
+        self
+    }
+
+    pub fn level(mut self, level: Level) -> Matcher {
+        self.level = Some(level);
+        self
+    }
+
+    pub fn target<S: Into<String>>(mut self, target: S) -> Matcher {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn msg<S: Into<String>>(mut self, msg: S) -> Matcher {
+        self.msg = Some(msg.into());
+        self
+    }
+
+    /// Return true if the record matches.
+    pub fn matches(&self, rec: &Captured) -> bool {
+        self.level.map_or(true, |l| l == rec.level)
+            && self.target.as_ref().map_or(true, |t| self.string(t, &rec.target))
+            && self.msg.as_ref().map_or(true, |m| self.string(m, &rec.msg))
+    }
+
+    fn string(&self, expected: &str, actual: &str) -> bool {
+        if self.glob {
+            let p: Vec<char> = expected.chars().collect();
+            let s: Vec<char> = actual.chars().collect();
+            glob(&p, &s)
+        } else {
+            expected == actual
+        }
+    }
+}
+
+// Return true if the pattern matches the entire string, iteratively with
+// backtracking to the last `*`.
+fn glob(p: &[char], s: &[char]) -> bool {
+    let (mut pi, mut si) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while si < s.len() {
+        if pi < p.len() && (p[pi] == '?' || (p[pi] != '*' && p[pi] == s[si])) {
+            pi += 1;
+            si += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, si));
+            pi += 1;
+        } else if let Some((spi, ssi)) = star {
+            pi = spi + 1;
+            si = ssi + 1;
+            star = Some((spi, ssi + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+// Return the records captured on the current thread, panicking if there is
+// no capture.
+fn current(assertion: &str) -> Vec<Captured> {
+    let buffer = CURRENT.with(|c| c.borrow().clone());
+    match buffer {
+        Some(b) => b.lock().unwrap_or_else(|e| e.into_inner()).records.clone(),
+        None => panic!(
+            "{} failed: no capture on this thread (see tao_log::testing::capture)",
+            assertion
+        ),
+    }
+}
+
+fn fail(assertion: &str, recs: &[Captured]) -> ! {
+    let mut out = format!("{} failed; captured records ({}):", assertion, recs.len());
+    for r in recs {
+        out.push_str("\n    ");
+        out.push_str(&r.to_string());
+    }
+    panic!("{}", out);
+}
+
+// Implementation of `assert_logged!`, `assert_logged_matches!` and
+// `assert_not_logged!`.
+#[doc(hidden)]
+pub fn __assert_logged(m: &Matcher, expected: bool, assertion: &str) {
+    let recs = current(assertion);
+    if recs.iter().any(|r| m.matches(r)) != expected {
+        fail(assertion, &recs);
+    }
+}
+
+// Implementation of `assert_logged_in_order!`: each matcher must match a
+// distinct record, in order, with any other records between.
+#[doc(hidden)]
+pub fn __assert_logged_in_order(ms: &[Matcher], assertion: &str) {
+    let recs = current(assertion);
+    let mut next = recs.iter();
+    for m in ms {
+        if !next.any(|r| m.matches(r)) {
+            fail(assertion, &recs);
+        }
+    }
+}
+
+// Print the captured records to stderr, on test failure.
+fn print_records(cap: &Capture) {
+    let recs = cap.records();
+    eprintln!("captured log records ({}):", recs.len());
+    for r in &recs {
+        eprintln!("    {}", r);
+    }
+}
+
+// Implementation of the `#[tao_log::test]` attribute: Run the test with a
+// capture, then any check, printing the captured records on failure.
+#[doc(hidden)]
+pub fn __run<T, F, C>(test: F, check: C) -> T
+    where F: FnOnce(&Capture) -> T, C: FnOnce(&Capture)
+{
+    let cap = capture();
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        let ret = test(&cap);
+        check(&cap);
+        ret
+    }));
+    match res {
+        Ok(ret) => ret,
+        Err(p) => {
+            print_records(&cap);
+            panic::resume_unwind(p)
+        }
+    }
+}
+
+// Implementation of the `#[tao_log::test]` attribute with `should_panic`:
+// Run the test with a capture, asserting that it panics, with a message
+// containing any expected string, then run any check.
+#[doc(hidden)]
+pub fn __run_should_panic<F, C>(expected: Option<&str>, test: F, check: C)
+    where F: FnOnce(&Capture), C: FnOnce(&Capture)
+{
+    let cap = capture();
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        let payload = match panic::catch_unwind(AssertUnwindSafe(|| test(&cap))) {
+            Ok(()) => panic!("test did not panic as expected"),
+            Err(p) => p,
+        };
+        if let Some(e) = expected {
+            let msg = payload_msg(&*payload).unwrap_or("");
+            if !msg.contains(e) {
+                panic!(
+                    "panic did not contain expected string\n      \
+                     panic message: {:?}\n expected substring: {:?}",
+                    msg, e
+                );
+            }
+        }
+        check(&cap);
+    }));
+    if let Err(p) = res {
+        print_records(&cap);
+        panic::resume_unwind(p)
+    }
+}
+
+struct CaptureLogger;
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        if log::set_logger(&LOGGER).is_err() {
+            panic!("tao_log::testing: another logger is already installed");
+        }
+        log::set_max_level(LevelFilter::Trace);
+    });
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, meta: &Metadata) -> bool {
+        CURRENT.try_with(|c| {
+            c.borrow().as_ref().map_or(false, |b| {
+                meta.level() <= b.lock().unwrap_or_else(|e| e.into_inner()).level
+            })
+        }).unwrap_or(false)
+    }
+
+    fn log(&self, record: &Record) {
+        let buffer = CURRENT.try_with(|c| c.borrow().clone());
+        if let Ok(Some(buffer)) = buffer {
+            let mut buffer = buffer.lock().unwrap_or_else(|e| e.into_inner());
+            if record.level() <= buffer.level {
+                buffer.records.push(captured(record));
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn captured(record: &Record) -> Captured {
+    Captured {
+        level: record.level(),
+        target: record.target().to_owned(),
+        msg: record.args().to_string(),
+        module_path: record.module_path().map(str::to_owned),
+        file: record.file().map(str::to_owned),
+        line: record.line(),
+        kvs: kvs(record),
+    }
+}
+
+#[cfg(feature = "kv")]
+fn kvs(record: &Record) -> Vec<(String, String)> {
+    struct Collect(Vec<(String, String)>);
+
+    impl<'kvs> log::kv::VisitSource<'kvs> for Collect {
+        fn visit_pair(
+            &mut self,
+            k: log::kv::Key<'kvs>,
+            v: log::kv::Value<'kvs>)
+            -> Result<(), log::kv::Error>
+        {
+            self.0.push((k.to_string(), v.to_string()));
+            Ok(())
+        }
+    }
+
+    let mut c = Collect(Vec::new());
+    let _ = record.key_values().visit(&mut c);
+    c.0
+}
+
+#[cfg(not(feature = "kv"))]
+fn kvs(_record: &Record) -> Vec<(String, String)> {
+    Vec::new()
+}
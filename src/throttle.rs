@@ -0,0 +1,75 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Per-callsite state for the throttled macros.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+/// Time based rate limit state, for a static per callsite.
+#[doc(hidden)]
+pub struct Throttle {
+    // Milliseconds since BASE, plus one, of the last emitted record, or zero
+    // if none.
+    last: AtomicUsize,
+    // Count of suppressed records since the last emitted.
+    suppressed: AtomicUsize,
+}
+
+impl Throttle {
+    pub const fn new() -> Throttle {
+        Throttle {
+            last: AtomicUsize::new(0),
+            suppressed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Return the count of suppressed records since the last emitted, if a
+    /// record should now be emitted, or `None` if suppressed, given the
+    /// minimum interval between records.
+    pub fn check(&self, interval: Duration) -> Option<usize> {
+        // Truncated, as wrapping differences are used
+        let now = (millis(since_base()) as usize).wrapping_add(1);
+        let last = self.last.load(Ordering::Relaxed);
+        let interval = millis(interval);
+        if last != 0 && (now.wrapping_sub(last) as u64) < interval {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        match self.last.compare_exchange(
+            last, now, Ordering::Relaxed, Ordering::Relaxed
+        ) {
+            Ok(_) => Some(self.suppressed.swap(0, Ordering::Relaxed)),
+            Err(_) => {
+                // Another thread emitted concurrently
+                self.suppressed.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Throttle {
+        Throttle::new()
+    }
+}
+
+// Return the duration since a process-wide base instant, set on first use.
+fn since_base() -> Duration {
+    static INIT: Once = Once::new();
+    static mut BASE: Option<Instant> = None;
+    INIT.call_once(|| {
+        unsafe { BASE = Some(Instant::now()); }
+    });
+    unsafe { BASE }.map_or(Duration::from_secs(0), |b| b.elapsed())
+}
+
+// Return whole milliseconds of the duration, saturating.
+fn millis(d: Duration) -> u64 {
+    d.as_secs()
+        .saturating_mul(1000)
+        .saturating_add(u64::from(d.subsec_millis()))
+}
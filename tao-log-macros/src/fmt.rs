@@ -0,0 +1,158 @@
+//! Validation of format string literals, and construction of format strings
+//! and templates.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::LitStr;
+
+/// Return the number of `{…}` placeholders in the given format string, or an
+/// error message if its braces are unbalanced. Escaped `{{` and `}}` are not
+/// counted.
+pub(crate) fn count_placeholders(fmt: &str) -> Result<usize, &'static str> {
+    let mut count = 0;
+    let mut chars = fmt.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '{' => {
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(_) => {}
+                        None => return Err("unterminated `{` in format string"),
+                    }
+                }
+                count += 1;
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+            }
+            '}' => return Err("unmatched `}` in format string"),
+            _ => {}
+        }
+    }
+    Ok(count)
+}
+
+
+/// A piece of a -v message: literal text (as escaped for a format string),
+/// the arrow, or a slot for the prefix, expression or value.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Piece {
+    Text(String),
+    Arrow,
+    Prefix,
+    Expr,
+    Value,
+}
+
+/// Parse a -v message template with `{prefix}`, `{expr}` and `{value}` slots
+/// into pieces, or return an error message. The template must contain a
+/// `{value}` slot, and any `{prefix}` slot must not be between the first and
+/// last `{expr}` or `{value}` slots, which are repeated for each expression.
+pub(crate) fn parse_template(tpl: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = tpl.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push_str("{{");
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(
+                            "unterminated `{` in template".to_owned()
+                        ),
+                    }
+                }
+                let slot = match name.as_str() {
+                    "prefix" => Piece::Prefix,
+                    "expr" => Piece::Expr,
+                    "value" => Piece::Value,
+                    _ => return Err(format!(
+                        "unknown template slot `{{{}}}`, expected `{{prefix}}`, \
+                         `{{expr}}` or `{{value}}`",
+                        name
+                    )),
+                };
+                if !text.is_empty() {
+                    pieces.push(Piece::Text(text.split_off(0)));
+                }
+                pieces.push(slot);
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push_str("}}");
+            }
+            '}' => return Err("unmatched `}` in template".to_owned()),
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+
+    if !pieces.contains(&Piece::Value) {
+        return Err("template must contain a `{value}` slot".to_owned());
+    }
+    let (first, last) = item_span(&pieces);
+    if pieces[first..last].contains(&Piece::Prefix) {
+        return Err(
+            "template `{prefix}` must not be between `{expr}` and `{value}` \
+             slots".to_owned()
+        );
+    }
+    Ok(pieces)
+}
+
+/// Return the range of pieces from the first to the last `{expr}` or
+/// `{value}` slot, which is repeated for each expression.
+pub(crate) fn item_span(pieces: &[Piece]) -> (usize, usize) {
+    let item = |p: &Piece| *p == Piece::Expr || *p == Piece::Value;
+    let first = pieces.iter().position(item).unwrap_or(0);
+    let last = pieces.iter().rposition(item).map_or(first, |l| l + 1);
+    (first, last)
+}
+
+/// A format string under construction, with each arrow left to the
+/// `__tao_arrow!` macro of the _tao-log_ crate, per its ascii-arrow feature.
+pub(crate) struct Fmt {
+    segments: Vec<String>,
+}
+
+impl Fmt {
+    pub(crate) fn new() -> Fmt {
+        Fmt { segments: vec![String::new()] }
+    }
+
+    pub(crate) fn push_str(&mut self, s: &str) {
+        self.segments.last_mut().unwrap().push_str(s);
+    }
+
+    pub(crate) fn push_arrow(&mut self) {
+        self.segments.push(String::new());
+    }
+
+    /// Return the format string as a literal, or `concat!` of the segments
+    /// and arrows, given the path of the _tao-log_ crate.
+    pub(crate) fn tokens<K: ToTokens>(&self, krate: &K) -> TokenStream {
+        let segs: Vec<LitStr> = self.segments.iter()
+            .map(|s| LitStr::new(s, Span::call_site()))
+            .collect();
+        if segs.len() == 1 {
+            let s = &segs[0];
+            return quote!(#s);
+        }
+        let first = &segs[0];
+        let rest = &segs[1..];
+        quote!(concat!(#first #(, #krate::__tao_arrow!(), #rest)*))
+    }
+}
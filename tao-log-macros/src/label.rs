@@ -0,0 +1,142 @@
+//! Labels of logged expressions, as their original source text.
+
+use proc_macro2::{Delimiter, LineColumn, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::LitStr;
+
+/// Return an expression for the label of an expression, given its tokens
+/// with their original spans, matching a `stringify!` of the same expression
+/// by the macro_rules -v macros.
+///
+/// Tokens output by a proc-macro lose any spacing other than between joint
+/// punctuation, such that a `stringify!` of them gives e.g. `"v [0]"` or `"&
+/// v"`. Where the source locations of the tokens are available (rustc
+/// 1.88+), the label is instead a literal of their source text, with
+/// whitespace normalized. Otherwise this falls back to `stringify!`.
+pub(crate) fn label(tokens: &TokenStream) -> TokenStream {
+    let mut atoms = Vec::new();
+    flatten(tokens, &mut atoms);
+    if atoms.is_empty() || atoms.iter().any(|a| a.start.line == 0) {
+        return quote!(stringify!(#tokens));
+    }
+    let mut text = String::new();
+    for (i, a) in atoms.iter().enumerate() {
+        if i > 0 {
+            let p = &atoms[i - 1];
+            if !same(p.end, a.start) && space_between(p, a) {
+                text.push(' ');
+            }
+        }
+        text.push_str(&a.text);
+    }
+    let lit = LitStr::new(&text, Span::call_site());
+    quote!(#lit)
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Ident,
+    Literal,
+    Punct(char),
+    Open(Delimiter),
+    Close,
+}
+
+// A token or group delimiter, with its text and source location.
+struct Atom {
+    text: String,
+    kind: Kind,
+    start: LineColumn,
+    end: LineColumn,
+}
+
+// Flatten tokens to atoms, looking through any invisible groups. Delimiters
+// are a single character, which also covers groups re-created with a single
+// span for both delimiters.
+fn flatten(tokens: &TokenStream, out: &mut Vec<Atom>) {
+    for tt in tokens.clone() {
+        match tt {
+            TokenTree::Group(g) => {
+                let (open, close) = match g.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => {
+                        flatten(&g.stream(), out);
+                        continue;
+                    }
+                };
+                let start = g.span_open().start();
+                out.push(Atom {
+                    text: open.to_owned(),
+                    kind: Kind::Open(g.delimiter()),
+                    start,
+                    end: LineColumn {
+                        line: start.line,
+                        column: start.column + 1,
+                    },
+                });
+                flatten(&g.stream(), out);
+                let end = g.span_close().end();
+                out.push(Atom {
+                    text: close.to_owned(),
+                    kind: Kind::Close,
+                    start: LineColumn {
+                        line: end.line,
+                        column: end.column.saturating_sub(1),
+                    },
+                    end,
+                });
+            }
+            TokenTree::Ident(i) => {
+                out.push(leaf(i.to_string(), Kind::Ident, i.span()));
+            }
+            TokenTree::Literal(l) => {
+                out.push(leaf(l.to_string(), Kind::Literal, l.span()));
+            }
+            TokenTree::Punct(p) => {
+                let c = p.as_char();
+                out.push(leaf(c.to_string(), Kind::Punct(c), p.span()));
+            }
+        }
+    }
+}
+
+fn leaf(text: String, kind: Kind, span: Span) -> Atom {
+    Atom { text, kind, start: span.start(), end: span.end() }
+}
+
+fn same(a: LineColumn, b: LineColumn) -> bool {
+    a.line == b.line && a.column == b.column
+}
+
+// Return true if the separated atoms should be printed with a space, with
+// the exceptions of `stringify!` in rustc: None after `.`, before `,`, `;`
+// or `.`, or between a (non-keyword) identifier and parentheses.
+fn space_between(prev: &Atom, next: &Atom) -> bool {
+    let punct = |a: &Atom| match a.kind {
+        Kind::Punct(_) => true,
+        _ => false,
+    };
+    match (prev.kind, next.kind) {
+        (Kind::Punct('.'), _) if !punct(next) => false,
+        (_, Kind::Punct(',')) | (_, Kind::Punct(';')) | (_, Kind::Punct('.'))
+            if !punct(prev) => false,
+        (Kind::Ident, Kind::Open(Delimiter::Parenthesis)) => {
+            keyword(&prev.text)
+        }
+        _ => true,
+    }
+}
+
+fn keyword(s: &str) -> bool {
+    match s {
+        "as" | "async" | "await" | "box" | "break" | "const" | "continue"
+            | "crate" | "dyn" | "else" | "enum" | "extern" | "false" | "for"
+            | "if" | "impl" | "in" | "let" | "loop" | "match" | "mod"
+            | "move" | "mut" | "ref" | "return" | "self" | "static"
+            | "struct" | "super" | "trait" | "true" | "type" | "unsafe"
+            | "use" | "where" | "while" | "yield" => true,
+        _ => false,
+    }
+}
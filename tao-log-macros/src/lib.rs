@@ -0,0 +1,135 @@
+// Copyright Ⓒ 2026 David Kellum
+//
+// Offered under the same Apache 2.0 or MIT licenses as _tao-log_.
+
+//! Procedural macro implementation for the _tao-log_ crate.
+//!
+//! This is an internal companion crate. It is used via the optional
+//! `proc-macro` feature of _tao-log_, and should not be depended on directly.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+mod fmt;
+mod label;
+mod logfn;
+mod logv;
+mod power;
+mod test;
+
+// Proc-macro replacement for the `__tao_logv!` helper, with validation of the
+// prefix and value format. The first argument is the `$crate` of tao-log.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __tao_logv_impl(input: TokenStream) -> TokenStream {
+    logv::expand(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+// Proc-macro replacement for the `__tao_fatal_assert!` helper, recording the
+// values of sub-expressions of the condition. The first argument is the
+// `$crate` of tao-log.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __tao_assert_impl(input: TokenStream) -> TokenStream {
+    power::expand_assert(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+// Proc-macro implementation of `logv_check!` and its per-level variants. The
+// first argument is the `$crate` of tao-log, and the second the level.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __tao_checkv_impl(input: TokenStream) -> TokenStream {
+    power::expand_check(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Log the arguments and return value of a function.
+///
+/// On entry, the named arguments of the function are logged in a single
+/// record, e.g. `split(a → 3, b → "x")`, and on exit, its return value, e.g.
+/// `split → (1, 2)`, in the same format as the _-v_ macros. The function
+/// body is otherwise unchanged, including `return` and `?`. Note that the
+/// arguments are logged by reference, while the return value is moved and
+/// then returned.
+///
+/// The following options are supported:
+///
+/// * `level = "debug"`: The log level, one of: error, warn, info, debug
+///   (default) or trace.
+/// * `target = "name"`: The logging target, defaulting to the module path.
+/// * `skip(a, b)`: Arguments not to log, e.g. those not implementing `Debug`.
+///   A `self` receiver is never logged.
+/// * `display(a)`: Arguments to log with their `Display`, instead of `Debug`,
+///   implementation.
+/// * `err = "error"`: The log level for an `Err` return value. This
+///   defaults to error if the return type is a `Result` (by name), and may
+///   also be set for an alias of a `Result`.
+/// * `ret(display)`: Log the return value with its `Display` implementation.
+/// * `ret(skip)`: Don't log the return value, e.g. for an `impl Iterator`.
+/// * `crate = "path"`: The path of the _tao-log_ crate, where renamed or
+///   re-exported, defaulting to `::tao_log`.
+///
+/// This requires the `proc-macro` feature.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tao_log::logfn;
+///
+/// #[logfn(level = "info", display(name))]
+/// fn greet(name: &str, count: usize) -> String {
+///     format!("hello {} x{}", name, count)
+/// }
+/// // ^-- info log: greet(name → world, count → 2)
+/// // ^-- info log: greet → "hello world x2"
+///
+/// #[logfn(target = "parse", skip(radix))]
+/// fn parse(s: &str, radix: u32) -> Result<i64, std::num::ParseIntError> {
+///     Ok(i64::from_str_radix(s, radix)?)
+/// }
+/// // ^-- debug log: parse(s → "x")
+/// // ^-- error log: parse → Err(ParseIntError { kind: InvalidDigit })
+///
+/// greet("world", 2);
+/// assert!(parse("x", 10).is_err());
+/// ```
+#[proc_macro_attribute]
+pub fn logfn(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(args as syn::AttributeArgs);
+    let item = syn::parse_macro_input!(item as syn::ItemFn);
+    logfn::expand(args, item)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Run a test with a capture of its log records.
+///
+/// Re-exported as `tao_log::testing::test` with the `proc-macro` and
+/// `testing` features. This replaces `#[test]`, starting a capture for the
+/// test thread, and printing all captured records if the test fails. The
+/// test function may optionally take a `&Capture` argument.
+///
+/// The following options are supported:
+///
+/// * `check = "path"`: A function taking a `&Capture`, called after the
+///   test function returns, or after it panics with `should_panic`. This
+///   allows assertions on the records logged prior to an expected panic.
+/// * `crate = "path"`: The path of the _tao-log_ crate, where renamed or
+///   re-exported, defaulting to `::tao_log`.
+///
+/// Any `should_panic` attribute is handled by this attribute, such that a
+/// failed check fails the test.
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = syn::parse_macro_input!(args as syn::AttributeArgs);
+    let item = syn::parse_macro_input!(item as syn::ItemFn);
+    test::expand(args, item)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
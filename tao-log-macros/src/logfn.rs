@@ -0,0 +1,270 @@
+//! Expansion of the `#[logfn]` attribute.
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    Error, FnArg, ItemFn, Lit, LitStr, Meta, NestedMeta, Pat, Path,
+    ReturnType, Type
+};
+
+use crate::fmt::Fmt;
+
+/// Options of the attribute.
+struct Options {
+    level: Ident,
+    target: Option<LitStr>,
+    err: Option<Ident>,
+    skip: Vec<Ident>,
+    display: Vec<Ident>,
+    ret: Ret,
+    krate: Path,
+}
+
+/// Logging of the return value.
+#[derive(PartialEq)]
+enum Ret {
+    Debug,
+    Display,
+    Skip,
+}
+
+impl Options {
+    fn parse(args: Vec<NestedMeta>) -> syn::Result<Options> {
+        let mut opts = Options {
+            level: Ident::new("Debug", Span::call_site()),
+            target: None,
+            err: None,
+            skip: Vec::new(),
+            display: Vec::new(),
+            ret: Ret::Debug,
+            krate: default_crate(),
+        };
+        for arg in args {
+            match arg {
+                NestedMeta::Meta(Meta::NameValue(ref nv)) => {
+                    let s = match nv.lit {
+                        Lit::Str(ref s) => s.clone(),
+                        ref l => return Err(Error::new_spanned(
+                            l, "expected a string literal"
+                        )),
+                    };
+                    if nv.path.is_ident("level") {
+                        opts.level = level(&s)?;
+                    } else if nv.path.is_ident("target") {
+                        opts.target = Some(s);
+                    } else if nv.path.is_ident("err") {
+                        opts.err = Some(level(&s)?);
+                    } else if nv.path.is_ident("crate") {
+                        opts.krate = s.parse()?;
+                    } else {
+                        return Err(unknown(&nv.path));
+                    }
+                }
+                NestedMeta::Meta(Meta::List(ref l)) if l.path.is_ident("ret") => {
+                    let mut nested = l.nested.iter();
+                    opts.ret = match (nested.next(), nested.next()) {
+                        (Some(NestedMeta::Meta(Meta::Path(p))), None)
+                            if p.is_ident("display") => Ret::Display,
+                        (Some(NestedMeta::Meta(Meta::Path(p))), None)
+                            if p.is_ident("skip") => Ret::Skip,
+                        _ => return Err(Error::new_spanned(
+                            l, "expected `ret(display)` or `ret(skip)`"
+                        )),
+                    };
+                }
+                NestedMeta::Meta(Meta::List(ref l)) => {
+                    let list = if l.path.is_ident("skip") {
+                        &mut opts.skip
+                    } else if l.path.is_ident("display") {
+                        &mut opts.display
+                    } else {
+                        return Err(unknown(&l.path));
+                    };
+                    for n in &l.nested {
+                        match n {
+                            NestedMeta::Meta(Meta::Path(p)) if p.get_ident().is_some() => {
+                                list.push(p.get_ident().unwrap().clone());
+                            }
+                            _ => return Err(Error::new_spanned(
+                                n, "expected an argument name"
+                            )),
+                        }
+                    }
+                }
+                ref a => return Err(unknown(a)),
+            }
+        }
+        Ok(opts)
+    }
+}
+
+fn unknown<T: quote::ToTokens>(tokens: &T) -> Error {
+    Error::new_spanned(
+        tokens,
+        "unknown option, expected one of: level, target, err, skip, display, ret, \
+         crate"
+    )
+}
+
+/// Return the default path of the _tao-log_ crate, as overridden by a `crate
+/// = "path"` option, where it is renamed or re-exported.
+pub(crate) fn default_crate() -> Path {
+    syn::parse_quote!(::tao_log)
+}
+
+/// Return the `log::Level` variant for the given level name.
+pub(crate) fn level(s: &LitStr) -> syn::Result<Ident> {
+    let name = match s.value().to_lowercase().as_str() {
+        "error" => "Error",
+        "warn"  => "Warn",
+        "info"  => "Info",
+        "debug" => "Debug",
+        "trace" => "Trace",
+        _ => return Err(Error::new_spanned(
+            s, "expected a level: error, warn, info, debug or trace"
+        )),
+    };
+    Ok(Ident::new(name, s.span()))
+}
+
+pub(crate) fn expand(args: Vec<NestedMeta>, item: ItemFn)
+    -> syn::Result<TokenStream>
+{
+    let opts = Options::parse(args)?;
+    let ItemFn { attrs, vis, sig, block } = item;
+    let name = sig.ident.to_string();
+
+    // Named (identifier pattern) arguments, excluding any receiver
+    let mut names = Vec::new();
+    for input in &sig.inputs {
+        if let FnArg::Typed(pt) = input {
+            if let Pat::Ident(ref pi) = *pt.pat {
+                names.push(pi.ident.clone());
+            }
+        }
+    }
+    for id in opts.skip.iter().chain(&opts.display) {
+        if !names.contains(id) {
+            return Err(Error::new_spanned(id, "not an argument of this function"));
+        }
+    }
+
+    let mut fmt = Fmt::new();
+    fmt.push_str(&format!("{}(", name));
+    let mut kvs = Vec::new();
+    let mut vals = Vec::new();
+    for id in names.iter().filter(|id| !opts.skip.contains(id)) {
+        if !vals.is_empty() {
+            fmt.push_str(", ");
+        }
+        let key = id.to_string();
+        if opts.display.contains(id) {
+            fmt.push_str(&format!("{} ", key));
+            fmt.push_arrow();
+            fmt.push_str(" {}");
+            kvs.push(quote!((#key, display #id)));
+        } else {
+            fmt.push_str(&format!("{} ", key));
+            fmt.push_arrow();
+            fmt.push_str(" {:?}");
+            kvs.push(quote!((#key, debug #id)));
+        }
+        vals.push(id);
+    }
+    fmt.push_str(")");
+    let krate = &opts.krate;
+    let fmt = fmt.tokens(&quote!(#krate));
+
+    let target = match opts.target {
+        Some(ref t) => quote!(#t),
+        None => quote!(module_path!()),
+    };
+    let level = &opts.level;
+    let level = quote!(#krate::log::Level::#level);
+
+    // Return type, for annotation if not containing an `impl Trait`, and
+    // whether it is (by name) a `Result`.
+    let (ret_type, is_result) = match sig.output {
+        ReturnType::Default => (Some(quote!(())), false),
+        ReturnType::Type(_, ref t) => {
+            let annotate = !has_impl(quote!(#t));
+            (if annotate { Some(quote!(#t)) } else { None }, is_result(t))
+        }
+    };
+
+    let ret = Ident::new("ret", Span::mixed_site());
+    let err_level = match opts.err {
+        Some(e) => Some(e),
+        None if is_result => Some(Ident::new("Error", Span::call_site())),
+        None => None,
+    };
+    let ret_level = match err_level {
+        Some(e) => quote! {
+            match #ret {
+                Err(_) => #krate::log::Level::#e,
+                _ => #level,
+            }
+        },
+        None => level.clone(),
+    };
+
+    let call = if sig.asyncness.is_some() {
+        quote!(async move #block.await)
+    } else {
+        match ret_type {
+            Some(ref t) => quote!((move || -> #t #block)()),
+            None => quote!((move || #block)()),
+        }
+    };
+    let let_type = ret_type.map(|t| quote!(: #t));
+
+    let exit = match opts.ret {
+        Ret::Debug => quote! {
+            #krate::__tao_v_log!(
+                #target, #ret_level, [("return", debug #ret)],
+                concat!("{} ", #krate::__tao_arrow!(), " {:?}"),
+                #name, &#ret
+            );
+        },
+        Ret::Display => quote! {
+            #krate::__tao_v_log!(
+                #target, #ret_level, [("return", display #ret)],
+                concat!("{} ", #krate::__tao_arrow!(), " {}"),
+                #name, &#ret
+            );
+        },
+        Ret::Skip => quote!(),
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #krate::__tao_v_log!(
+                #target, #level, [#(#kvs),*], #fmt #(, &#vals)*
+            );
+            #[allow(clippy::redundant_closure_call)]
+            let #ret #let_type = #call;
+            #exit
+            #ret
+        }
+    })
+}
+
+// Return true if the type tokens include an `impl Trait`.
+fn has_impl(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|t| match t {
+        TokenTree::Ident(ref i) => i == "impl",
+        TokenTree::Group(ref g) => has_impl(g.stream()),
+        _ => false,
+    })
+}
+
+// Return true if the type is a path with last segment `Result`.
+fn is_result(t: &Type) -> bool {
+    match t {
+        Type::Path(tp) => tp.path.segments.last()
+            .map_or(false, |s| s.ident == "Result"),
+        Type::Group(g) => is_result(&g.elem),
+        _ => false,
+    }
+}
@@ -0,0 +1,371 @@
+//! Expansion of the -v macros.
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Expr, Lit, Token};
+
+use crate::fmt::{count_placeholders, item_span, parse_template, Fmt, Piece};
+use crate::label::label;
+
+mod kw {
+    syn::custom_keyword!(suppressed);
+    syn::custom_keyword!(target);
+}
+
+/// Parsed input: `$crate, level, [@suppressed expr,] [target: expr,] args...`
+struct Input {
+    krate: TokenTree,
+    level: Expr,
+    suppressed: Option<Expr>,
+    target: Option<Expr>,
+    args: Vec<Arg>,
+}
+
+/// An argument expression and its original tokens, for its label.
+struct Arg {
+    tokens: TokenStream,
+    expr: Expr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let level = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let mut suppressed = None;
+        if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            input.parse::<kw::suppressed>()?;
+            suppressed = Some(input.parse()?);
+            input.parse::<Token![,]>()?;
+        }
+        let mut target = None;
+        if input.peek(kw::target)
+            && input.peek2(Token![:])
+            && !input.peek2(Token![::])
+        {
+            input.parse::<kw::target>()?;
+            input.parse::<Token![:]>()?;
+            target = Some(input.parse()?);
+            input.parse::<Token![,]>()?;
+        }
+        let args = parse_args(input)?;
+        Ok(Input { krate, level, suppressed, target, args })
+    }
+}
+
+// Parse comma separated argument expressions with an optional trailing
+// comma, retaining the original tokens of each.
+fn parse_args(input: ParseStream) -> syn::Result<Vec<Arg>> {
+    let mut args = Vec::new();
+    while !input.is_empty() {
+        let fork = input.fork();
+        let expr = fork.parse()?;
+        let tokens = input.step(|c| {
+            let mut rest = *c;
+            let mut tokens = TokenStream::new();
+            while rest != fork.cursor() {
+                match rest.token_tree() {
+                    Some((tt, next)) => {
+                        tokens.extend(Some(tt));
+                        rest = next;
+                    }
+                    None => break,
+                }
+            }
+            Ok((tokens, rest))
+        })?;
+        args.push(Arg { tokens, expr });
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+    Ok(args)
+}
+
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let Input { krate, level, suppressed, target, args } =
+        syn::parse2(input)?;
+    let mut exprs = args;
+    if exprs.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "expected at least one expression to log"
+        ));
+    }
+
+    // A leading `template = "<template>"`, then a leading literal is the
+    // prefix, or a leading `prefix = <expr>` the runtime prefix, and a
+    // following literal the value format, as long as an expression remains.
+    let mut template = None;
+    let mut prefix = None;
+    let mut rt_prefix = None;
+    let mut vfmt = None;
+    if let Some(tpl) = named_arg(&exprs[0].expr, "template") {
+        template = Some(template_pieces(tpl)?);
+        if exprs.len() == 1 {
+            return Err(Error::new_spanned(
+                &exprs[0].tokens,
+                "expected at least one expression to log after the template"
+            ));
+        }
+        exprs.remove(0);
+    }
+    if let Some(pre) = named_arg(&exprs[0].expr, "prefix") {
+        if exprs.len() == 1 {
+            return Err(Error::new_spanned(
+                &exprs[0].tokens,
+                "expected at least one expression to log after the prefix"
+            ));
+        }
+        rt_prefix = Some(pre.clone());
+        exprs.remove(0);
+    } else if exprs.len() > 1 {
+        if let Some(lit) = literal(&exprs[0].expr) {
+            prefix = Some(prefix_str(lit)?);
+            exprs.remove(0);
+        }
+    }
+    if (prefix.is_some() || rt_prefix.is_some()) && exprs.len() > 1 {
+        if let Some(lit) = literal(&exprs[0].expr) {
+            vfmt = Some(value_format(lit)?);
+            exprs.remove(0);
+        }
+    }
+    let prefix = prefix.filter(|p| !p.is_empty());
+
+    // Each expression may be named, as `<name> = <expr>`, with the name as
+    // its label instead of the stringified (original tokens of the)
+    // expression. A parenthesized `(a = b)` remains an assignment.
+    let (labels, exprs): (Vec<TokenStream>, Vec<Expr>) = exprs.into_iter()
+        .map(|a| match named_value(&a.expr) {
+            Some((name, exp)) => (quote!(stringify!(#name)), exp.clone()),
+            None => (label(&a.tokens), a.expr),
+        })
+        .unzip();
+
+    // Without a template, the default is equivalent to `"{prefix} {expr} →
+    // {value}"`, but without the space if there is no prefix.
+    let pieces = template.unwrap_or_else(|| {
+        let mut d = Vec::new();
+        if prefix.is_some() || rt_prefix.is_some() {
+            d.push(Piece::Prefix);
+            d.push(Piece::Text(" ".to_owned()));
+        }
+        d.push(Piece::Expr);
+        d.push(Piece::Text(" ".to_owned()));
+        d.push(Piece::Arrow);
+        d.push(Piece::Text(" ".to_owned()));
+        d.push(Piece::Value);
+        d
+    });
+
+    let vs: Vec<Ident> = if exprs.len() == 1 {
+        vec![Ident::new("vt", Span::mixed_site())]
+    } else {
+        (0..exprs.len())
+            .map(|i| Ident::new(&format!("vt{}", i), Span::mixed_site()))
+            .collect()
+    };
+    let pre = Ident::new("pre", Span::mixed_site());
+
+    // The format string and its arguments, in order of the slots, with the
+    // item span of the pieces repeated for each expression.
+    let mut fmt = Fmt::new();
+    let mut fargs = Vec::new();
+    {
+        let mut render = |piece: &Piece, i: usize| match piece {
+            Piece::Text(t) => fmt.push_str(t),
+            Piece::Arrow => fmt.push_arrow(),
+            Piece::Prefix => {
+                if let Some(ref p) = prefix {
+                    fmt.push_str(p);
+                } else if rt_prefix.is_some() {
+                    fmt.push_str("{}");
+                    fargs.push(quote!(#pre));
+                }
+            }
+            Piece::Expr => {
+                fmt.push_str("{}");
+                fargs.push(labels[i].clone());
+            }
+            Piece::Value => {
+                fmt.push_str(vfmt.as_ref().map_or("{:?}", |f| f.as_str()));
+                let v = &vs[i];
+                fargs.push(quote!(&#v));
+            }
+        };
+        let (first, last) = item_span(&pieces);
+        for p in &pieces[..first] {
+            render(p, 0);
+        }
+        for i in 0..exprs.len() {
+            if i > 0 {
+                render(&Piece::Text(", ".to_owned()), i);
+            }
+            for p in &pieces[first..last] {
+                render(p, i);
+            }
+        }
+        for p in &pieces[last..] {
+            render(p, 0);
+        }
+    }
+    let fmt = fmt.tokens(&krate);
+
+    let target = match target {
+        Some(t) => quote!(#t),
+        None => quote!(module_path!()),
+    };
+
+    // Key-value pairs for the optional kv feature, with a custom value
+    // format applied via `Display` of the formatted arguments.
+    let mut kvs = Vec::new();
+    if let Some(p) = prefix {
+        kvs.push(quote!(("prefix", literal #p)));
+    } else if rt_prefix.is_some() {
+        kvs.push(quote!(("prefix", display #pre)));
+    }
+    for (label, v) in labels.iter().zip(&vs) {
+        kvs.push(match vfmt {
+            Some(ref f) => quote!((#label, display format_args!(#f, #v))),
+            None => quote!((#label, debug #v)),
+        });
+    }
+
+    // A runtime prefix is evaluated once, only if the level is enabled, and
+    // only logged if the template includes it.
+    let log = match rt_prefix {
+        Some(p) => {
+            let lvl = Ident::new("lvl", Span::mixed_site());
+            quote! {
+                let #lvl = #level;
+                if #krate::__tao_enabled!(#lvl) {
+                    #[allow(unused_variables)]
+                    let #pre = &#p;
+                    #krate::__tao_v_suppressed!(
+                        #target, #lvl, [#suppressed], [#(#kvs),*],
+                        #fmt, #(#fargs),*
+                    );
+                }
+            }
+        }
+        None => quote! {
+            #krate::__tao_v_suppressed!(
+                #target, #level, [#suppressed], [#(#kvs),*],
+                #fmt, #(#fargs),*
+            );
+        },
+    };
+
+    if exprs.len() == 1 {
+        let exp = &exprs[0];
+        let v = &vs[0];
+        Ok(quote! {
+            match (#exp,) {
+                (#v,) => {
+                    #log
+                    #v
+                }
+            }
+        })
+    } else {
+        Ok(quote! {
+            match (#(#exprs,)*) {
+                (#(#vs,)*) => {
+                    #log
+                    (#(#vs,)*)
+                }
+            }
+        })
+    }
+}
+
+// Return the literal of an expression, if it is one, looking through any
+// invisible group from a macro_rules fragment.
+pub(crate) fn literal(exp: &Expr) -> Option<&Lit> {
+    match exp {
+        Expr::Lit(l) => Some(&l.lit),
+        Expr::Group(g) => literal(&g.expr),
+        _ => None,
+    }
+}
+
+// Return the value of a `<name> = <expr>` argument, if it is one.
+fn named_arg<'a>(exp: &'a Expr, name: &str) -> Option<&'a Expr> {
+    match named_value(exp) {
+        Some((n, value)) if n == name => Some(value),
+        _ => None,
+    }
+}
+
+// Return the name and value of any `<name> = <expr>` argument.
+fn named_value(exp: &Expr) -> Option<(&Ident, &Expr)> {
+    match exp {
+        Expr::Assign(a) if a.attrs.is_empty() => match &*a.left {
+            Expr::Path(p) if p.qself.is_none() && p.attrs.is_empty() => {
+                p.path.get_ident().map(|name| (name, &*a.right))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn template_pieces(tpl: &Expr) -> syn::Result<Vec<Piece>> {
+    match literal(tpl) {
+        Some(Lit::Str(s)) => {
+            parse_template(&s.value()).map_err(|e| Error::new_spanned(s, e))
+        }
+        _ => Err(Error::new_spanned(tpl, "expected a string literal template")),
+    }
+}
+
+pub(crate) fn prefix_str(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(s) => {
+            let p = s.value();
+            match count_placeholders(&p) {
+                Ok(0) => Ok(p),
+                Ok(_) => Err(Error::new_spanned(
+                    s,
+                    "prefix must not contain format placeholders \
+                     (escape literal braces as `{{` and `}}`)"
+                )),
+                Err(e) => Err(Error::new_spanned(s, e)),
+            }
+        }
+        _ => Err(Error::new_spanned(
+            lit,
+            "expected a string literal prefix (wrap a literal value to log \
+             in parentheses, e.g. `(1)`)"
+        )),
+    }
+}
+
+fn value_format(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(s) => {
+            let f = s.value();
+            match count_placeholders(&f) {
+                Ok(1) => Ok(f),
+                Ok(n) => Err(Error::new_spanned(
+                    s,
+                    format!(
+                        "value format must contain exactly one placeholder, \
+                         found {}",
+                        n
+                    )
+                )),
+                Err(e) => Err(Error::new_spanned(s, e)),
+            }
+        }
+        _ => Err(Error::new_spanned(
+            lit,
+            "expected a string literal value format"
+        )),
+    }
+}
@@ -0,0 +1,391 @@
+//! Expansion of power-assert style conditions, recording the values of
+//! sub-expressions of `&&`, `||` and comparison trees.
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{BinOp, Error, Expr, Token};
+
+use crate::fmt::Fmt;
+use crate::label::label;
+use crate::logv::{literal, prefix_str};
+
+mod kw {
+    syn::custom_keyword!(target);
+}
+
+/// Which nodes are recorded.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Mode {
+    /// Only false nodes, as needed to explain a failed condition. Any nodes
+    /// recorded under a true `||` are removed, so a condition with `||` is
+    /// first evaluated without recording.
+    Fail,
+
+    /// All evaluated nodes.
+    All,
+}
+
+/// Parsed input: `$crate, target, condition [, format args...]`
+struct AssertInput {
+    krate: TokenTree,
+    target: Expr,
+    cond: Expr,
+    args: Option<TokenStream>,
+}
+
+impl Parse for AssertInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let target = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let cond = input.parse()?;
+        let mut args = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            args = Some(input.parse()?);
+        }
+        Ok(AssertInput { krate, target, cond, args })
+    }
+}
+
+pub(crate) fn expand_assert(input: TokenStream) -> syn::Result<TokenStream> {
+    let AssertInput { krate, target, cond, args } = syn::parse2(input)?;
+    let rec = Ident::new("rec", Span::mixed_site());
+    let check = Gen { krate: &krate, rec: &rec, mode: Mode::Fail }
+        .node(&cond, 0);
+    let cond_label = label(&quote!(#cond));
+    let fatal = match args {
+        Some(args) => quote! {
+            #krate::__tao_fatal!(
+                #target, None, "assertion failed: {}: {}{}",
+                #cond_label, format_args!(#args), #rec
+            )
+        },
+        None => quote! {
+            #krate::__tao_fatal!(
+                #target, None, "assertion failed: {}{}",
+                #cond_label, #rec
+            )
+        },
+    };
+    let values = values(&krate);
+
+    // Without `||`, nothing is recorded for a true condition, since any false
+    // node makes it false. Otherwise, it is evaluated again on failure, since
+    // the nodes recorded for a false `||` operand are only removed later.
+    if has_or(&cond) {
+        Ok(quote! {
+            if !(#cond) {
+                #values
+                let mut #rec = #krate::__private::Explain::new();
+                let _ = #check;
+                #fatal
+            }
+        })
+    } else {
+        Ok(quote! {{
+            #values
+            #[allow(unused_mut)]
+            let mut #rec = #krate::__private::Explain::new();
+            if !(#check) {
+                #fatal
+            }
+        }})
+    }
+}
+
+// The traits of `Probe`, imported for its autoref specialization.
+fn values(krate: &TokenTree) -> TokenStream {
+    quote! {
+        #[allow(unused_imports)]
+        use #krate::__private::{DebugValue as _, OtherValue as _};
+    }
+}
+
+/// Parsed input: `$crate, level, [target: expr,] args...`
+struct CheckInput {
+    krate: TokenTree,
+    level: Expr,
+    target: Option<Expr>,
+    args: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for CheckInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let level = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let mut target = None;
+        if input.peek(kw::target)
+            && input.peek2(Token![:])
+            && !input.peek2(Token![::])
+        {
+            input.parse::<kw::target>()?;
+            input.parse::<Token![:]>()?;
+            target = Some(input.parse()?);
+            input.parse::<Token![,]>()?;
+        }
+        let args = Punctuated::parse_terminated(input)?;
+        Ok(CheckInput { krate, level, target, args })
+    }
+}
+
+pub(crate) fn expand_check(input: TokenStream) -> syn::Result<TokenStream> {
+    let CheckInput { krate, level, target, args } = syn::parse2(input)?;
+    let mut args: Vec<Expr> = args.into_iter().collect();
+    let cond = match args.pop() {
+        Some(c) => c,
+        None => return Err(Error::new(
+            Span::call_site(), "expected a condition to check"
+        )),
+    };
+
+    // Optional prefix, preceding the condition
+    let mut prefix = None;
+    if let Some(p) = args.pop() {
+        match literal(&p) {
+            Some(lit) => prefix = Some(prefix_str(lit)?),
+            None => args.push(p),
+        }
+    }
+    if let Some(extra) = args.first() {
+        return Err(Error::new_spanned(
+            extra,
+            "unexpected argument, expected: [target: <expr>,] [\"prefix\",] \
+             <condition>"
+        ));
+    }
+    let prefix = prefix.filter(|p| !p.is_empty());
+
+    let mut fmt = Fmt::new();
+    let mut kvs = Vec::new();
+    if let Some(ref p) = prefix {
+        fmt.push_str(p);
+        fmt.push_str(" ");
+        kvs.push(quote!(("prefix", literal #p)));
+    }
+    fmt.push_str("{} ");
+    fmt.push_arrow();
+    fmt.push_str(" {:?}{}");
+    let fmt = fmt.tokens(&krate);
+
+    let target = match target {
+        Some(t) => quote!(#t),
+        None => quote!(module_path!()),
+    };
+
+    let rec = Ident::new("rec", Span::mixed_site());
+    let lvl = Ident::new("lvl", Span::mixed_site());
+    let v = Ident::new("v", Span::mixed_site());
+    let check = Gen { krate: &krate, rec: &rec, mode: Mode::All }
+        .node(&cond, 0);
+    let cond_label = label(&quote!(#cond));
+    let values = values(&krate);
+    Ok(quote! {{
+        #values
+        let #lvl = #level;
+        if #krate::__tao_enabled!(#lvl) {
+            let mut #rec = #krate::__private::Explain::new();
+            let #v: bool = #check;
+            #krate::__tao_v_log!(
+                #target, #lvl,
+                [#(#kvs,)* (#cond_label, debug #v)],
+                #fmt, #cond_label, #v, #rec
+            );
+            #v
+        } else {
+            #cond
+        }
+    }})
+}
+
+/// Generator of the recording evaluation of a condition.
+pub(crate) struct Gen<'a> {
+    pub(crate) krate: &'a TokenTree,
+    pub(crate) rec: &'a Ident,
+    pub(crate) mode: Mode,
+}
+
+impl<'a> Gen<'a> {
+    /// Return an expression evaluating the condition as `bool`, with short
+    /// circuit semantics preserved, recording nodes at the given depth.
+    pub(crate) fn node(&self, exp: &Expr, depth: usize) -> TokenStream {
+        let exp = strip(exp);
+        if let Expr::Binary(ref b) = *exp {
+            match b.op {
+                BinOp::And(_) | BinOp::Or(_) => {
+                    return self.logical(exp, &b.op, depth);
+                }
+                BinOp::Eq(_) | BinOp::Ne(_) | BinOp::Lt(_) | BinOp::Le(_)
+                    | BinOp::Gt(_) | BinOp::Ge(_) =>
+                {
+                    return self.compare(exp, &b.left, &b.op, &b.right, depth);
+                }
+                _ => {}
+            }
+        }
+        self.leaf(exp, depth)
+    }
+
+    // A chain of the same `&&` or `||` operator, with each operand a child.
+    fn logical(&self, exp: &Expr, op: &BinOp, depth: usize) -> TokenStream {
+        let mut operands = Vec::new();
+        flatten(exp, op, &mut operands);
+        let children: Vec<TokenStream> = operands.iter()
+            .map(|o| self.node(o, depth + 1))
+            .collect();
+        let rec = self.rec;
+        let at = Ident::new("at", Span::mixed_site());
+        let v = Ident::new("v", Span::mixed_site());
+        let lbl = label(&quote!(#exp));
+        let value = self.value(quote!(&#v));
+        let record = quote! {
+            #rec.record(#at, #depth, #lbl, #value)
+        };
+        let (eval, record) = match *op {
+            BinOp::And(_) => (
+                quote!(#(#children)&&*),
+                self.unless(&v, record, quote!())
+            ),
+            _ => (
+                quote!(#(#children)||*),
+                self.unless(&v, record, quote!(#rec.truncate(#at)))
+            ),
+        };
+        quote! {{
+            let #at = #rec.len();
+            let #v: bool = #eval;
+            #record
+            #v
+        }}
+    }
+
+    // A comparison, with each non-literal operand a child.
+    fn compare(
+        &self,
+        exp: &Expr,
+        left: &Expr,
+        op: &BinOp,
+        right: &Expr,
+        depth: usize)
+        -> TokenStream
+    {
+        let rec = self.rec;
+        let l = Ident::new("l", Span::mixed_site());
+        let r = Ident::new("r", Span::mixed_site());
+        let v = Ident::new("v", Span::mixed_site());
+        let d1 = depth + 1;
+        let lbl = label(&quote!(#exp));
+        let value = self.value(quote!(&#v));
+        let mut record = vec![quote! {
+            #rec.record(#rec.len(), #depth, #lbl, #value);
+        }];
+        for (o, id) in [(left, &l), (right, &r)].iter() {
+            if !is_literal(o) {
+                let lbl = label(&quote!(#o));
+                let value = self.value(quote!(#id));
+                record.push(quote! {
+                    #rec.record(#rec.len(), #d1, #lbl, #value);
+                });
+            }
+        }
+        let record = self.unless(&v, quote!(#(#record)*), quote!());
+        quote! {
+            match (&(#left), &(#right)) {
+                (#l, #r) => {
+                    let #v: bool = *#l #op *#r;
+                    #record
+                    #v
+                }
+            }
+        }
+    }
+
+    // Any other boolean expression.
+    fn leaf(&self, exp: &Expr, depth: usize) -> TokenStream {
+        let rec = self.rec;
+        let v = Ident::new("v", Span::mixed_site());
+        let lbl = label(&quote!(#exp));
+        let value = self.value(quote!(&#v));
+        let record = self.unless(&v, quote! {
+            #rec.record(#rec.len(), #depth, #lbl, #value)
+        }, quote!());
+        quote! {{
+            let #v: bool = #exp;
+            #record
+            #v
+        }}
+    }
+
+    // Return the recorded value of the given reference, formatted via
+    // `Debug` if implemented, and otherwise `None`.
+    fn value(&self, reference: TokenStream) -> TokenStream {
+        let krate = self.krate;
+        quote!((&#krate::__private::Probe(#reference)).explain_value())
+    }
+
+    // Return the recording of a node, per mode, given its value, the record
+    // statement, and an alternative statement for a true value.
+    fn unless(&self, v: &Ident, record: TokenStream, alt: TokenStream)
+        -> TokenStream
+    {
+        match self.mode {
+            Mode::Fail => if alt.is_empty() {
+                quote!(if !#v { #record; })
+            } else {
+                quote!(if #v { #alt; } else { #record; })
+            },
+            Mode::All => quote!(#record;),
+        }
+    }
+}
+
+// Strip any parentheses or invisible groups.
+fn strip(exp: &Expr) -> &Expr {
+    match *exp {
+        Expr::Paren(ref p) => strip(&p.expr),
+        Expr::Group(ref g) => strip(&g.expr),
+        _ => exp,
+    }
+}
+
+// Collect the operands of a left associative chain of the same operator.
+fn flatten<'e>(exp: &'e Expr, op: &BinOp, out: &mut Vec<&'e Expr>) {
+    if let Expr::Binary(ref b) = *strip(exp) {
+        if same_op(&b.op, op) {
+            flatten(&b.left, op, out);
+            out.push(&b.right);
+            return;
+        }
+    }
+    out.push(exp);
+}
+
+// Return true if the condition has an `||` node, as traversed by `Gen`.
+fn has_or(exp: &Expr) -> bool {
+    match *strip(exp) {
+        Expr::Binary(ref b) => match b.op {
+            BinOp::Or(_) => true,
+            BinOp::And(_) => has_or(&b.left) || has_or(&b.right),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn same_op(a: &BinOp, b: &BinOp) -> bool {
+    match (a, b) {
+        (BinOp::And(_), BinOp::And(_)) | (BinOp::Or(_), BinOp::Or(_)) => true,
+        _ => false,
+    }
+}
+
+fn is_literal(exp: &Expr) -> bool {
+    match *strip(exp) {
+        Expr::Lit(_) => true,
+        _ => false,
+    }
+}
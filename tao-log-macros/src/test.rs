@@ -0,0 +1,137 @@
+//! Expansion of the `#[test]` attribute.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, ItemFn, Lit, LitStr, Meta, NestedMeta, Path};
+
+use crate::logfn::default_crate;
+
+/// Options of the attribute.
+struct Options {
+    check: Option<Path>,
+    krate: Path,
+}
+
+impl Options {
+    fn parse(args: Vec<NestedMeta>) -> syn::Result<Options> {
+        let mut opts = Options { check: None, krate: default_crate() };
+        for arg in args {
+            match arg {
+                NestedMeta::Meta(Meta::NameValue(ref nv))
+                    if nv.path.is_ident("check") =>
+                {
+                    match nv.lit {
+                        Lit::Str(ref s) => opts.check = Some(s.parse()?),
+                        ref l => return Err(Error::new_spanned(
+                            l, "expected a string literal function path"
+                        )),
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(ref nv))
+                    if nv.path.is_ident("crate") =>
+                {
+                    match nv.lit {
+                        Lit::Str(ref s) => opts.krate = s.parse()?,
+                        ref l => return Err(Error::new_spanned(
+                            l, "expected a string literal crate path"
+                        )),
+                    }
+                }
+                ref a => return Err(Error::new_spanned(
+                    a, "unknown option, expected: check, crate"
+                )),
+            }
+        }
+        Ok(opts)
+    }
+}
+
+pub(crate) fn expand(args: Vec<NestedMeta>, item: ItemFn)
+    -> syn::Result<TokenStream>
+{
+    let opts = Options::parse(args)?;
+    let ItemFn { attrs, vis, sig, block } = item;
+    if sig.asyncness.is_some() || !sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &sig, "a test function can't be async or generic"
+        ));
+    }
+    if sig.inputs.len() > 1 {
+        return Err(Error::new_spanned(
+            &sig.inputs,
+            "expected no arguments, or a `&Capture` argument"
+        ));
+    }
+
+    // Any `should_panic` is handled here, so that the check may run after
+    // the panic is caught.
+    let mut should_panic: Option<Option<LitStr>> = None;
+    let mut keep = Vec::new();
+    for attr in attrs {
+        if attr.path.is_ident("should_panic") {
+            should_panic = Some(expected(attr.parse_meta()?)?);
+        } else {
+            keep.push(attr);
+        }
+    }
+
+    let name = &sig.ident;
+    let output = &sig.output;
+    let inputs = &sig.inputs;
+    let call = if inputs.is_empty() {
+        quote!(|_| #name())
+    } else {
+        quote!(|cap| #name(cap))
+    };
+    let check = match opts.check {
+        Some(ref c) => quote!(|cap| #c(cap)),
+        None => quote!(|_| {}),
+    };
+    let krate = &opts.krate;
+    let run = match should_panic {
+        Some(Some(ref e)) => quote! {
+            #krate::testing::__run_should_panic(Some(#e), #call, #check)
+        },
+        Some(None) => quote! {
+            #krate::testing::__run_should_panic(None, #call, #check)
+        },
+        None => quote! {
+            #krate::testing::__run(#call, #check)
+        },
+    };
+
+    Ok(quote! {
+        #[test]
+        #(#keep)*
+        #vis fn #name() #output {
+            fn #name(#inputs) #output #block
+            #run
+        }
+    })
+}
+
+// Return the expected message of a `should_panic` attribute, if any.
+fn expected(meta: Meta) -> syn::Result<Option<LitStr>> {
+    match meta {
+        Meta::Path(_) => Ok(None),
+        Meta::NameValue(ref nv) => match nv.lit {
+            Lit::Str(ref s) => Ok(Some(s.clone())),
+            ref l => Err(Error::new_spanned(l, "expected a string literal")),
+        },
+        Meta::List(ref l) => {
+            for n in &l.nested {
+                match n {
+                    NestedMeta::Meta(Meta::NameValue(nv))
+                        if nv.path.is_ident("expected") =>
+                    {
+                        if let Lit::Str(ref s) = nv.lit {
+                            return Ok(Some(s.clone()));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Err(Error::new_spanned(l, "expected `should_panic(expected = \"…\")`"))
+        }
+    }
+}
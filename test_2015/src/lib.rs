@@ -26,7 +26,114 @@ fn test_2015_logv_macros() {
     debugv!(v);
     infov!("prefix", v);
     warnv!("prefix", "{:?}", v);
+    assert_eq!(debugv!(v, !v), (true, false));
     assert!(errorv!(v));
+    let ctx = String::from("ctx");
+    assert!(infov!(prefix = &ctx, v));
+    assert_eq!(warnv!(prefix = ctx, "{}", v, !v), (true, false));
+    assert!(debugv!(named = v));
+    assert_eq!(infov!("prefix", "{}", named = v, !v), (true, false));
+}
+
+#[test]
+fn test_2015_resultv_macros() {
+    let r: Result<u8, std::fmt::Error> = Ok(1);
+    logv_result!(target: "special", Level::Trace, r).unwrap();
+    tracev_result!(r).unwrap();
+    debugv_result!(err: Level::Warn, r).unwrap();
+    infov_result!(sources, "prefix", r).unwrap();
+    assert_eq!(warnv_result!(r), Ok(1));
+    assert_eq!(errorv_result!(r), Ok(1));
+}
+
+#[test]
+fn test_2015_tryv_macro() {
+    fn parse(s: &str) -> Result<u8, std::num::ParseIntError> {
+        let v: u8 = tryv!(target: "special", err: Level::Warn, "prefix", s.parse());
+        Ok(v + tryv!(Ok::<u8, std::num::ParseIntError>(1)))
+    }
+    fn first(v: &[u8]) -> Option<u8> {
+        Some(*tryv!(v.first()))
+    }
+    assert_eq!(parse("1"), Ok(2));
+    assert!(parse("x").is_err());
+    assert_eq!(first(&[]), None);
+}
+
+#[test]
+fn test_2015_time_macros() {
+    let v = true;
+    logv_time!(target: "special", Level::Trace, v);
+    tracev_time!(novalue, v);
+    debugv_time!("prefix", v);
+    infov_time!("prefix", "{:?}", v);
+    assert!(warnv_time!(v));
+    assert!(errorv_time!(novalue, "prefix", v));
+}
+
+#[test]
+fn test_2015_slowv_macro() {
+    let v = true;
+    let limit = std::time::Duration::from_millis(10);
+    slowv!(target: "special", level: Level::Trace, limit, v);
+    slowv!(limit, "prefix", v);
+    assert!(slowv!(limit, v));
+}
+
+#[test]
+fn test_2015_scopev_macro() {
+    let _s1 = scopev!(target: "special", Level::Trace, "s1");
+    let _s2 = scopev!(target: "special", "s2");
+    let _s3 = scopev!(Level::Info, "s3");
+    let _s4 = scopev!("s4");
+}
+
+#[test]
+fn test_2015_once_every_n_macros() {
+    let v = true;
+    log_once!(target: "special", Level::Trace, "{}", v);
+    trace_once!("trace");
+    debug_once!("debug");
+    info_once!("info");
+    warn_once!(target: "special", "warn");
+    error_once!("error");
+    logv_once!(target: "special", Level::Trace, v);
+    tracev_once!(v);
+    debugv_once!(v);
+    infov_once!("prefix", v);
+    warnv_once!("prefix", "{:?}", v);
+    assert!(errorv_once!(v));
+
+    log_every_n!(2, target: "special", Level::Trace, "{}", v);
+    trace_every_n!(2, "trace");
+    debug_every_n!(2, "debug");
+    info_every_n!(2, "info");
+    warn_every_n!(2, target: "special", "warn");
+    error_every_n!(2, "error");
+    logv_every_n!(2, target: "special", Level::Trace, v);
+    tracev_every_n!(2, v);
+    debugv_every_n!(2, v);
+    infov_every_n!(2, "prefix", v);
+    warnv_every_n!(2, "prefix", "{:?}", v);
+    assert_eq!(errorv_every_n!(2, v, !v), (true, false));
+}
+
+#[test]
+fn test_2015_throttled_macros() {
+    let v = true;
+    let iv = std::time::Duration::from_secs(1);
+    log_throttled!(iv, target: "special", Level::Trace, "{}", v);
+    trace_throttled!(iv, "trace");
+    debug_throttled!(iv, "debug");
+    info_throttled!(iv, "info");
+    warn_throttled!(iv, target: "special", "warn");
+    error_throttled!(iv, "error");
+    logv_throttled!(iv, target: "special", Level::Trace, v);
+    tracev_throttled!(iv, v);
+    debugv_throttled!(iv, target: "special", v);
+    infov_throttled!(iv, "prefix", v);
+    warnv_throttled!(iv, "prefix", "{:?}", v);
+    assert_eq!(errorv_throttled!(iv, v, !v), (true, false));
 }
 
 #[test]
@@ -40,3 +147,57 @@ fn test_2015_fatal_static_msg() {
 fn test_2015_fatal_format_msg() {
     fatal!("fmt {}", "failing");
 }
+
+#[test]
+#[should_panic]
+fn test_2015_fatal_exit() {
+    let code = 3;
+    if code == 0 {
+        fatal!(exit: code, "exit fatal msg");
+    }
+    if code == 1 {
+        fatal!(target: "special", exit: code, "exit {}", "failing");
+    }
+    fatal!(target: "special", "fmt {}", "failing");
+}
+
+#[test]
+fn test_2015_chain_macros() {
+    let e = "x".parse::<u8>().unwrap_err();
+    let e = log_chain!(Level::Info, "prefix", e);
+    log_chain!(target: "special", Level::Info, multiline, &e);
+    error_chain!(target: "special", "while", &e);
+    warn_chain!(backtrace, &e);
+    info_chain!(multiline, backtrace, "p", &e);
+    debug_chain!(&e);
+    trace_chain!(&e);
+}
+
+#[test]
+fn test_2015_fatal_assert() {
+    let (a, b) = (1, 2);
+    fatal_assert!(a < b);
+    fatal_assert!(target: "special", a < b && b > 0, "msg {}", a);
+    fatal_assert_eq!(a + 1, b);
+    fatal_assert_eq!(target: "special", a * 2, b, "msg {}", a);
+}
+
+#[test]
+#[should_panic]
+fn test_2015_fatal_assert_fail() {
+    let (a, b) = (1, 2);
+    fatal_assert!(a > b || b == 3);
+}
+
+#[test]
+fn test_2015_lazy_v_macros() {
+    let v = 3;
+    let l = Level::Info;
+    assert_eq!(logl!(l, v), None);
+    assert_eq!(logl!(target: "special", l, "prefix", "{}", v), None);
+    assert_eq!(errorl!(v), None);
+    assert_eq!(warnl!(target: "special", v, v + 1), None);
+    assert_eq!(infol!("prefix", v), None);
+    assert_eq!(debugl!(v), None);
+    assert_eq!(tracel!(v), None);
+}
@@ -0,0 +1,26 @@
+//! Tests of logfn attribute options (proc-macro feature)
+
+extern crate tao_log;
+use tao_log::logfn;
+
+#[logfn(level = "loud")]
+fn bad_level(a: i32) -> i32 {
+    a
+}
+
+#[logfn(skip(b))]
+fn skip_non_argument(a: i32) -> i32 {
+    a
+}
+
+#[logfn(quiet)]
+fn unknown_option(a: i32) -> i32 {
+    a
+}
+
+#[logfn(ret(hex))]
+fn bad_ret(a: i32) -> i32 {
+    a
+}
+
+fn main() {}
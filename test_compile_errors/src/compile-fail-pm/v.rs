@@ -0,0 +1,35 @@
+//! Tests of -v macro prefix and value format errors (proc-macro feature)
+
+extern crate tao_log;
+use tao_log::*;
+
+fn non_string_prefix() {
+    warnv!(666, 1);
+}
+
+fn placeholder_in_prefix() {
+    let i = 4;
+    warnv!("bad prefix with {:?}", i);
+}
+
+fn unmatched_brace_in_prefix() {
+    let i = 4;
+    warnv!("bad prefix }", i);
+}
+
+fn missing_placeholder_in_value_format() {
+    let i = 4;
+    warnv!("prefix", "no place", i);
+}
+
+fn extra_placeholder_in_value_format() {
+    let i = 4;
+    warnv!("prefix", "{} and {:?}", i, i);
+}
+
+fn non_string_value_format() {
+    let i = 4;
+    warnv!("prefix", 7, i);
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+//! Tests of *v argument count (proc-macro feature)
+
+extern crate tao_log;
+use tao_log::*;
+
+fn zero_arguments() {
+    infov!();
+}
+
+fn zero_arguments_target() {
+    infov!(target: "special",);
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+//! Tests of *v argument commas (proc-macro feature)
+
+extern crate tao_log;
+use tao_log::*;
+
+fn zero_arguments_comma() {
+    infov!(,);
+}
+
+fn multi_trailing_comma() {
+    infov!(33,,);
+}
+
+fn main() {}
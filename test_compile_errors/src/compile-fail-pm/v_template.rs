@@ -0,0 +1,31 @@
+//! Tests of -v macro template errors (proc-macro feature)
+
+extern crate tao_log;
+use tao_log::*;
+
+fn unknown_slot() {
+    let i = 4;
+    warnv!(template = "{expr} = {val}", i);
+}
+
+fn missing_value_slot() {
+    let i = 4;
+    warnv!(template = "{prefix} {expr}", i);
+}
+
+fn prefix_between_slots() {
+    let i = 4;
+    warnv!(template = "{expr} {prefix} {value}", "p", i);
+}
+
+fn unterminated_slot() {
+    let i = 4;
+    warnv!(template = "{expr} = {value", i);
+}
+
+fn non_string_template() {
+    let i = 4;
+    warnv!(template = 7, i);
+}
+
+fn main() {}
@@ -7,8 +7,4 @@ fn zero_arguments() {
     infov!();
 }
 
-fn four_arguments() {
-    infov!("prefix", "{}", true, 4);
-}
-
 fn main() {}
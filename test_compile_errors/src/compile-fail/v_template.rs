@@ -0,0 +1,11 @@
+//! Test of -v macro template without the proc-macro feature
+
+extern crate tao_log;
+use tao_log::*;
+
+fn template_requires_proc_macro() {
+    let i = 4;
+    warnv!(template = "{expr} = {value}", i);
+}
+
+fn main() {}
@@ -1,5 +1,8 @@
 #[test]
 fn compile_test() {
     let t = trybuild::TestCases::new();
+    #[cfg(not(feature = "proc-macro"))]
     t.compile_fail("src/compile-fail/*.rs");
+    #[cfg(feature = "proc-macro")]
+    t.compile_fail("src/compile-fail-pm/*.rs");
 }
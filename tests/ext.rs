@@ -0,0 +1,88 @@
+//! Tests of the `Result` and `Option` logging extension traits (testing
+//! feature)
+
+use std::num::ParseIntError;
+use std::panic;
+
+use log::Level;
+use tao_log::*;
+use tao_log::ext::{LogOptionExt, LogResultExt};
+use tao_log::fatal::FatalError;
+use tao_log::testing;
+
+fn parse(s: &str) -> Result<u8, ParseIntError> {
+    s.parse()
+}
+
+#[test]
+fn result_log() {
+    let cap = testing::capture();
+    let line = line!() + 1;
+    let r = parse("x").log_ok(Level::Info).log_err(Level::Warn);
+    assert!(r.is_err());
+    assert_eq!(cap.records().len(), 1);
+    let rec = cap.last().unwrap();
+    assert_eq!(rec.level, Level::Warn);
+    assert_eq!(rec.target, "tao_log::ext");
+    assert_eq!(rec.msg, "Err(ParseIntError { kind: InvalidDigit })");
+    assert_eq!(rec.file.as_ref().map(String::as_str), Some(file!()));
+    assert_eq!(rec.line, Some(line));
+
+    cap.clear();
+    assert_eq!(parse("7").log_err(Level::Warn).log_ok(Level::Info), Ok(7));
+    assert_logged!(level = Info, msg = "Ok(7)");
+    assert_not_logged!(level = Warn);
+}
+
+#[test]
+fn option_log() {
+    let cap = testing::capture();
+    assert_eq!(Some(3).log_none(Level::Debug), Some(3));
+    assert!(cap.records().is_empty());
+    let line = line!() + 1;
+    assert_eq!(None::<u8>.log_none(Level::Debug), None);
+    let rec = cap.last().unwrap();
+    assert_eq!(rec.level, Level::Debug);
+    assert_eq!(rec.msg, "None");
+    assert_eq!(rec.line, Some(line));
+}
+
+#[test]
+fn disabled_level() {
+    let cap = testing::capture_level(log::LevelFilter::Info);
+    assert!(parse("x").log_err(Level::Debug).is_err());
+    assert!(cap.records().is_empty());
+}
+
+#[test]
+fn unwrap_or_fatal() {
+    let cap = testing::capture();
+    assert_eq!(parse("7").unwrap_or_fatal("parsing"), 7);
+    assert_eq!(Some(3).unwrap_or_fatal("some"), 3);
+    assert!(cap.records().is_empty());
+
+    let prior = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let line = line!() + 1;
+    let res = panic::catch_unwind(|| parse("x").unwrap_or_fatal("parsing x"));
+    let res2 = panic::catch_unwind(|| None::<u8>.unwrap_or_fatal("no value"));
+    panic::set_hook(prior);
+
+    let payload = res.unwrap_err();
+    let fe = FatalError::from_payload(&payload).unwrap();
+    assert_eq!(fe.message(), "parsing x: ParseIntError { kind: InvalidDigit }");
+    assert_eq!(fe.target(), "tao_log::ext");
+    assert_eq!(fe.module_path(), None);
+    assert_eq!(fe.file(), Some(file!()));
+    assert_eq!(fe.line(), Some(line));
+    let fe = FatalError::from_payload(&res2.unwrap_err()).unwrap().clone();
+    assert_eq!(fe.message(), "no value");
+    assert_eq!(fe.line(), Some(line + 1));
+
+    let recs = cap.records();
+    assert_eq!(recs.len(), 2);
+    assert_eq!(recs[0].level, Level::Error);
+    assert_eq!(recs[0].msg, "parsing x: ParseIntError { kind: InvalidDigit }");
+    assert_eq!(recs[0].line, Some(line));
+    assert_eq!(recs[1].msg, "no value");
+}
@@ -1,12 +1,20 @@
 //! Tests of fatal macro
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::panic;
+use std::process::{Command, Output};
 use std::sync::{Once, Arc};
 
 use log::{Log, Record, Metadata};
 #[cfg(feature = "std")] use log::set_boxed_logger;
 
-use tao_log::fatal;
+use tao_log::{fatal, fatal_assert, fatal_assert_eq};
+use tao_log::fatal::{
+    fatal_handler, set_fatal_handler, FatalError, FatalHandler
+};
 use parking_lot::{ReentrantMutex, ReentrantMutexGuard};
 
 #[cfg(not(feature = "std"))]
@@ -22,7 +30,7 @@ impl State {
     fn take(&self) -> Option<String> {
         self.last_log.lock().replace(None)
     }
-    fn lock(&self) -> ReentrantMutexGuard<RefCell<Option<String>>> {
+    fn lock(&self) -> ReentrantMutexGuard<'_, RefCell<Option<String>>> {
         self.last_log.lock()
     }
 }
@@ -35,7 +43,7 @@ impl Log for Logger {
     }
 
     fn log(&self, record: &Record) {
-        let msg = format!("{}", record.args());
+        let msg = arrows(format!("{}", record.args()));
         self.0.last_log.lock().replace(Some(msg));
         eprintln!("{:5} {}", record.level(), record.args());
     }
@@ -97,3 +105,297 @@ fn fatal_format_msg() {
     let _fr = FormatMsgCheck(s2);
     fatal!(target: "grim", "fmt {} msg", "fatal".to_owned());
 }
+
+// Run the named test of this executable in a child process, in which `child`
+// is true, and return its output.
+fn run_child(test: &str) -> Output {
+    Command::new(env::current_exe().unwrap())
+        .args(&["--exact", test, "--nocapture", "--test-threads", "1"])
+        .env("TAO_LOG_FATAL_CHILD", "1")
+        .output()
+        .unwrap()
+}
+
+fn child() -> bool {
+    env::var_os("TAO_LOG_FATAL_CHILD").is_some()
+}
+
+#[test]
+fn fatal_exit_code() {
+    if child() {
+        test_logger();
+        fatal!(exit: 3, "exit {} msg", "fatal");
+    }
+    let out = run_child("fatal_exit_code");
+    assert_eq!(out.status.code(), Some(3));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("ERROR exit fatal msg\nflushed!\n"), "{}", err);
+    assert!(!err.contains("panicked"), "{}", err);
+}
+
+#[test]
+fn fatal_handler_exit() {
+    if child() {
+        test_logger();
+        set_fatal_handler(FatalHandler::Exit(4));
+        assert_eq!(fatal_handler(), FatalHandler::Exit(4));
+        fatal!(target: "grim", "handler exit msg");
+    }
+    let out = run_child("fatal_handler_exit");
+    assert_eq!(out.status.code(), Some(4));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("ERROR handler exit msg\nflushed!\n"), "{}", err);
+}
+
+#[test]
+fn fatal_handler_abort() {
+    if child() {
+        test_logger();
+        set_fatal_handler(FatalHandler::Abort);
+        fatal!("abort fatal msg");
+    }
+    let out = run_child("fatal_handler_abort");
+    assert!(!out.status.success());
+    assert_ne!(out.status.code(), Some(101)); // not a panic
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("ERROR abort fatal msg\nflushed!\n"), "{}", err);
+}
+
+#[test]
+fn fatal_handler_default() {
+    assert_eq!(FatalHandler::default(), FatalHandler::Panic);
+    if !child() {
+        assert_eq!(fatal_handler(), FatalHandler::Panic);
+    }
+}
+
+#[test]
+fn fatal_payload() {
+    let s1 = test_logger();
+    let _test_guard = s1.lock();
+    let line = line!() + 2;
+    let res = panic::catch_unwind(|| {
+        fatal!(target: "grim", "payload {} msg", "fatal");
+    });
+    assert_eq!(s1.take(), Some("payload fatal msg".to_owned()));
+    let payload = res.unwrap_err();
+    let fe = FatalError::from_payload(&payload).unwrap();
+    assert_eq!(fe.message(), "payload fatal msg");
+    assert_eq!(fe.target(), "grim");
+    assert_eq!(fe.module_path(), Some("fatal"));
+    assert_eq!(fe.file(), Some(file!()));
+    assert_eq!(fe.line(), Some(line));
+    assert_eq!(fe.to_string(), "payload fatal msg");
+    let e: &dyn Error = fe;
+    assert!(e.source().is_none());
+
+    // Also via the borrowed payload
+    assert!(FatalError::from_payload(&*payload).is_some());
+
+    let res = panic::catch_unwind(|| panic!("plain"));
+    assert!(FatalError::from_payload(&res.unwrap_err()).is_none());
+}
+
+#[test]
+fn fatal_panic_hook() {
+    if child() {
+        test_logger();
+        panic::set_hook(Box::new(|info| {
+            let l = info.location().unwrap();
+            eprintln!("prior hook: {}:{}", l.file(), l.line());
+        }));
+        fatal::install_hook();
+        fatal::install_hook(); // only once
+        let res = panic::catch_unwind(|| panic!("plain {} msg", "panic"));
+        assert!(res.is_err());
+        fatal!("hook fatal msg");
+    }
+    let out = run_child("fatal_panic_hook");
+    assert_eq!(out.status.code(), Some(101));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(!err.contains("plain panic msg"), "{}", err);
+    assert!(err.contains("ERROR hook fatal msg\nflushed!\n\
+                          fatal: hook fatal msg\n\
+                          prior hook: tests/fatal.rs:"), "{}", err);
+    assert_eq!(err.matches("fatal: ").count(), 1, "{}", err);
+    assert_eq!(err.matches("prior hook: ").count(), 2, "{}", err);
+}
+
+#[test]
+fn fatal_default_hook() {
+    if child() {
+        test_logger();
+        fatal!("default hook fatal msg");
+    }
+    let out = run_child("fatal_default_hook");
+    assert_eq!(out.status.code(), Some(101));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("ERROR default hook fatal msg\nflushed!\n\
+                          fatal: default hook fatal msg\n"), "{}", err);
+    assert!(err.contains("panicked at"), "{}", err);
+}
+
+// Run the closure, returning the message of the expected fatal panic.
+fn fatal_msg<F: FnOnce() + panic::UnwindSafe>(f: F) -> String {
+    let payload = panic::catch_unwind(f).unwrap_err();
+    arrows(FatalError::from_payload(&payload).unwrap().message().to_owned())
+}
+
+// With the ascii-arrow feature, check that no arrows remain, then restore
+// them, for the same assertions.
+fn arrows(msg: String) -> String {
+    #[cfg(feature = "ascii-arrow")]
+    let msg = {
+        assert!(!msg.contains('→'), "{}", msg);
+        msg.replace("->", "→")
+    };
+    msg
+}
+
+#[test]
+fn fatal_assert_pass() {
+    let s1 = test_logger();
+    let _test_guard = s1.lock();
+    let mut calls = 0;
+    let mut call = |b: bool| { calls += 1; b };
+    fatal_assert!(call(true) && (call(false) || call(true)));
+    fatal_assert!(call(false) || 1 < 2, "never {}", "logged");
+    fatal_assert_eq!(call(true), true);
+    assert_eq!(calls, 5);
+    assert_eq!(s1.take(), None);
+}
+
+#[test]
+fn fatal_assert_fail() {
+    let s1 = test_logger();
+    let _test_guard = s1.lock();
+    let limit = 2;
+    let v: Vec<u8> = (1..4).collect();
+    let flag = true;
+    let msg = fatal_msg(|| {
+        fatal_assert!(target: "grim", v.len() <= limit && flag, "while {}", "checking");
+    });
+    assert_eq!(s1.take(), Some(msg.clone()));
+    #[cfg(feature = "proc-macro")]
+    assert_eq!(
+        msg,
+        "assertion failed: v.len() <= limit && flag: while checking\n  \
+         v.len() <= limit → false\n    \
+         v.len() → 3\n    \
+         limit → 2"
+    );
+    #[cfg(not(feature = "proc-macro"))]
+    assert_eq!(
+        msg,
+        "assertion failed: v.len() <= limit && flag: while checking"
+    );
+
+    let name = "tao".to_owned();
+    let msg = fatal_msg(|| fatal_assert!(flag && name.is_empty()));
+    #[cfg(feature = "proc-macro")]
+    assert_eq!(
+        msg,
+        "assertion failed: flag && name.is_empty()\n  \
+         name.is_empty() → false"
+    );
+    #[cfg(not(feature = "proc-macro"))]
+    assert_eq!(msg, "assertion failed: flag && name.is_empty()");
+
+    let msg = fatal_msg(|| fatal_assert!(v.is_empty()));
+    assert_eq!(msg, "assertion failed: v.is_empty()");
+    assert_eq!(s1.take(), Some(msg));
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn fatal_assert_explain() {
+    let s1 = test_logger();
+    let _test_guard = s1.lock();
+    let (a, b, s) = (1, 2, "x".to_owned());
+
+    // Or: all false operands, with short circuit of the nested and
+    let mut calls = 0;
+    let msg = fatal_msg(panic::AssertUnwindSafe(|| {
+        fatal_assert!(a > b || s == "y" || (a == 0 && { calls += 1; true }));
+    }));
+    assert_eq!(calls, 0);
+    assert_eq!(
+        msg,
+        "assertion failed: a > b || s == \"y\" || (a == 0 && { calls += 1; true })\n  \
+         a > b → false\n    \
+         a → 1\n    \
+         b → 2\n  \
+         s == \"y\" → false\n    \
+         s → \"x\"\n  \
+         a == 0 && { calls += 1; true } → false\n    \
+         a == 0 → false\n      \
+         a → 1"
+    );
+
+    // True or-operands are not recorded
+    let msg = fatal_msg(|| fatal_assert!((a < b || s.is_empty()) && b == 3));
+    assert_eq!(
+        msg,
+        "assertion failed: (a < b || s.is_empty()) && b == 3\n  \
+         b == 3 → false\n    \
+         b → 2"
+    );
+    assert_eq!(s1.take(), Some(msg));
+}
+
+#[test]
+fn fatal_assert_unexplained() {
+    let s1 = test_logger();
+    let _test_guard = s1.lock();
+
+    // A value counting its Debug formatting
+    thread_local!(static FORMATS: Cell<usize> = Cell::new(0));
+    #[derive(PartialEq)]
+    struct Counted(u8);
+    impl fmt::Debug for Counted {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            FORMATS.with(|c| c.set(c.get() + 1));
+            write!(f, "Counted({})", self.0)
+        }
+    }
+
+    // Nothing is formatted on success, including false or-operands
+    let (a, b) = (Counted(1), Counted(2));
+    fatal_assert!(a == b || a != b);
+    fatal_assert!((a == b || b == Counted(2)) && a != b);
+    assert_eq!(FORMATS.with(Cell::get), 0);
+
+    // Operands not implementing Debug are omitted
+    #[derive(PartialEq)]
+    struct Opaque(u8);
+    let (c, d) = (Opaque(1), Opaque(2));
+    let msg = fatal_msg(|| fatal_assert!(c == d || a == b));
+    #[cfg(feature = "proc-macro")]
+    assert_eq!(
+        msg,
+        "assertion failed: c == d || a == b\n  \
+         c == d → false\n  \
+         a == b → false\n    \
+         a → Counted(1)\n    \
+         b → Counted(2)"
+    );
+    #[cfg(not(feature = "proc-macro"))]
+    assert_eq!(msg, "assertion failed: c == d || a == b");
+    assert_eq!(s1.take(), Some(msg));
+}
+
+#[test]
+fn fatal_assert_eq_fail() {
+    let s1 = test_logger();
+    let _test_guard = s1.lock();
+    let (a, b) = (1, 2);
+    let msg = fatal_msg(|| fatal_assert_eq!(a + 1, b * 2));
+    assert_eq!(msg, "assertion failed: a + 1 == b * 2\n  a + 1 → 2\n  b * 2 → 4");
+    let s = "x".to_owned();
+    let msg = fatal_msg(|| {
+        fatal_assert_eq!(target: "grim", s, "y", "for {}", a);
+    });
+    assert_eq!(msg, "assertion failed: s == \"y\": for 1\n  s → \"x\"\n  \"y\" → \"y\"");
+    assert_eq!(s1.take(), Some(msg));
+    assert_eq!(s, "x"); // not moved
+}
@@ -15,12 +15,37 @@ fn set_boxed_logger(logger: Box<dyn Log>) -> Result<(), log::SetLoggerError> {
 
 struct State {
     last_log: Mutex<Option<String>>,
+    #[cfg(feature = "kv")]
+    last_kvs: Mutex<Vec<(String, String)>>,
 }
 
 fn last(state: &State) -> Option<String> {
     state.last_log.lock().unwrap().take()
 }
 
+#[cfg(feature = "kv")]
+fn last_kvs(state: &State) -> Vec<(String, String)> {
+    std::mem::replace(&mut *state.last_kvs.lock().unwrap(), Vec::new())
+}
+
+#[cfg(feature = "kv")]
+fn kvs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[cfg(feature = "kv")]
+struct KvCollect(Vec<(String, String)>);
+
+#[cfg(feature = "kv")]
+impl<'kvs> log::kv::VisitSource<'kvs> for KvCollect {
+    fn visit_pair(&mut self, k: log::kv::Key<'kvs>, v: log::kv::Value<'kvs>)
+        -> Result<(), log::kv::Error>
+    {
+        self.0.push((k.to_string(), v.to_string()));
+        Ok(())
+    }
+}
+
 struct Logger(Arc<State>);
 
 impl Log for Logger {
@@ -30,7 +55,20 @@ impl Log for Logger {
 
     fn log(&self, record: &Record) {
         let msg = format!("{}", record.args());
+
+        // With the ascii-arrow feature, check that no arrows remain, then
+        // restore them, for the same assertions.
+        #[cfg(feature = "ascii-arrow")]
+        let msg = {
+            assert!(!msg.contains('→') && !msg.contains('←'), "{}", msg);
+            msg.replace("->", "→").replace("<-", "←")
+        };
         *self.0.last_log.lock().unwrap() = Some(msg);
+        #[cfg(feature = "kv")] {
+            let mut c = KvCollect(Vec::new());
+            record.key_values().visit(&mut c).unwrap();
+            *self.0.last_kvs.lock().unwrap() = c.0;
+        }
         assert_eq!(record.file(), Some(file!()));
         assert!(record.line().is_some());
         let t = record.target().to_owned();
@@ -42,7 +80,11 @@ impl Log for Logger {
 }
 
 fn main() {
-    let me = Arc::new(State { last_log: Mutex::new(None) });
+    let me = Arc::new(State {
+        last_log: Mutex::new(None),
+        #[cfg(feature = "kv")]
+        last_kvs: Mutex::new(Vec::new()),
+    });
     let a = me.clone();
     set_boxed_logger(Box::new(Logger(me))).unwrap();
 
@@ -55,8 +97,8 @@ fn main() {
     assert_eq!(debugv!(i), 32);
     assert_eq!(last(&a), Some("i → 32".to_owned()));
 
-    // More expression (note reformatting via `stringify!`)
-    assert_eq!(debugv!(i+1), 33);
+    // More expression (logged as written, with normalized whitespace)
+    assert_eq!(debugv!(i + 1), 33);
     assert_eq!(last(&a), Some("i + 1 → 33".to_owned()));
 
     // Use special target (note target assert in Logger::log)
@@ -73,15 +115,82 @@ fn main() {
 
     // Explicit tuple for multiple expressions
     let j = 19;
-    let (q, r) = debugv!((j/4, j%4));
+    let (q, r) = debugv!((j / 4, j % 4));
     assert_eq!(q, 4);
     assert_eq!(r, 3);
     assert_eq!(last(&a), Some("(j / 4, j % 4) → (4, 3)".to_owned()));
 
     // Explicit tuple and custom prefix
-    assert_eq!(debugv!("fifth", (j/5, j%5)), (3, 4));
+    assert_eq!(debugv!("fifth", (j / 5, j % 5)), (3, 4));
     assert_eq!(last(&a), Some("fifth (j / 5, j % 5) → (3, 4)".to_owned()));
 
+    // Multiple expressions, logged in one record and returned as tuple
+    let vt = "foo";
+    let (q, r) = debugv!(j / 4, j % 4);
+    assert_eq!((q, r), (4, 3));
+    assert_eq!(last(&a), Some("j / 4 → 4, j % 4 → 3".to_owned()));
+
+    // Multiple expressions with prefix, custom format and target
+    assert_eq!(infov!("fifth", j / 5, vt), (3, "foo"));
+    assert_eq!(last(&a), Some("fifth j / 5 → 3, vt → \"foo\"".to_owned()));
+    assert_eq!(errorv!(target: "special", "hex", "{:#x}", j, i, 3), (19, 32, 3));
+    assert_eq!(last(&a), Some("hex j → 0x13, i → 0x20, 3 → 0x3".to_owned()));
+
+    // Multiple expressions are each evaluated exactly once, in order
+    let mut o = Some(7);
+    assert_eq!(debugv!(o.take(), o.take(), -j,), (Some(7), None, -19));
+    assert_eq!(last(&a), Some("o.take() → Some(7), o.take() → None, -j → -19".to_owned()));
+
+    // Parentheses needed for a literal value in first position
+    #[allow(unused_parens)] {
+        assert_eq!(debugv!((1), 2), (1, 2));
+        assert_eq!(last(&a), Some("(1) → 1, 2 → 2".to_owned()));
+    }
+
+    // Runtime prefix of any `Display` type, with optional value format
+    let ctx = format!("req-{}", 7);
+    assert_eq!(warnv!(prefix = &ctx, i), 32);
+    assert_eq!(last(&a), Some("req-7 i → 32".to_owned()));
+    assert_eq!(infov!(target: "special", prefix = ctx, "{:#x}", i), 32);
+    assert_eq!(last(&a), Some("req-7 i → 0x20".to_owned()));
+    assert_eq!(debugv!(prefix = 3, j / 5, vt), (3, "foo"));
+    assert_eq!(last(&a), Some("3 j / 5 → 3, vt → \"foo\"".to_owned()));
+    assert_eq!(debugv!(prefix = ctx, "{}", -j, vt), (-19, "foo"));
+    assert_eq!(last(&a), Some("req-7 -j → -19, vt → foo".to_owned()));
+
+    // Runtime prefix is evaluated once, and only if enabled
+    let mut calls = 0;
+    let mut prefix = || { calls += 1; "p" };
+    assert_eq!(debugv!(prefix = prefix(), i), 32);
+    assert_eq!(last(&a), Some("p i → 32".to_owned()));
+    assert_eq!(tracev!(prefix = prefix(), i), 32);
+    assert_eq!(last(&a), None);
+    assert_eq!(debugv_once!(prefix = prefix(), i, j), (32, 19));
+    assert_eq!(last(&a), Some("p i → 32, j → 19".to_owned()));
+    assert_eq!(tracel!(prefix = prefix(), i), None);
+    assert_eq!(calls, 2);
+
+    // Named values, logged with the name instead of the expression
+    let nums = [1, 2, 3];
+    assert_eq!(debugv!(len = nums.len()), 3);
+    assert_eq!(last(&a), Some("len → 3".to_owned()));
+    let sum = infov!(target: "special", "nums", "{:x}", sum = nums.iter().sum::<i32>() * 10);
+    assert_eq!(sum, 60);
+    assert_eq!(last(&a), Some("nums sum → 3c".to_owned()));
+    assert_eq!(debugv!(len = nums.len(), i, first = nums[0],), (3, 32, 1));
+    assert_eq!(last(&a), Some("len → 3, i → 32, first → 1".to_owned()));
+    assert_eq!(debugv!(prefix = ctx, "{}", neg = -j, vt), (-19, "foo"));
+    assert_eq!(last(&a), Some("req-7 neg → -19, vt → foo".to_owned()));
+    // (spacing of a leading unary `-` in the label varies by toolchain)
+    assert_eq!(debugv!("pair", -j, pos = j), (-19, 19));
+    let m = last(&a).unwrap();
+    assert!(m.starts_with("pair -") && m.ends_with("j → -19, pos → 19"), "{}", m);
+    assert_eq!(debugv_once!("once", n = i, nums.len()), (32, 3));
+    assert_eq!(last(&a), Some("once n → 32, nums.len() → 3".to_owned()));
+    assert_eq!(tracel!(n = i), None);
+    assert_eq!(errorv!(target: "special", target = i), 32);
+    assert_eq!(last(&a), Some("target → 32".to_owned()));
+
     // Syntactic edge case of single value tuple
     assert_eq!(debugv!((j,)), (19,));
     #[allow(unused_parens)] {
@@ -99,7 +208,7 @@ fn main() {
 
     // Trace disabled, expression still returned, but no log
     let i = 2;
-    assert_eq!(tracev!(i*4), 8);
+    assert_eq!(tracev!(i * 4), 8);
     assert_eq!(last(&a), None);
 
     // v* macros expand and evaluate the expression exactly _once_.
@@ -115,7 +224,7 @@ fn main() {
     assert_eq!(last(&a), Some("i → 3".to_owned()));
 
     // logv, default target, tuple
-    assert_eq!(logv!(Level::Warn, (i+1, i+2)).1, 5);
+    assert_eq!(logv!(Level::Warn, (i + 1, i + 2)).1, 5);
     assert_eq!(last(&a), Some("(i + 1, i + 2) → (4, 5)".to_owned()));
 
     // void function, statement position
@@ -129,40 +238,521 @@ fn main() {
     assert_eq!(s, "returned");
     assert_eq!(last(&a), Some("fstr() → \"returned\"".to_owned()));
 
-    // wrapping assignments is possible, but not very interesting, given
-    // rust doesn't emit the value, even with Copy types.
+    // wrapping assignments is possible, in parentheses to distinguish from
+    // a named value, but not very interesting, given rust doesn't emit the
+    // value, even with Copy types.
     let mut m = 0;
     assert_eq!(m, 0);
-    infov!(m = 1);
+    infov!((m = 1));
     assert_eq!(m, 1);
-    infov! { m = 2 };
+    infov! { (m = 2) };
+    assert_eq!(m, 2);
+    assert_eq!(last(&a), Some("(m = 2) → ()".to_owned()));
+    assert_eq!(infov!(m = 3), 3);
     assert_eq!(m, 2);
-    assert_eq!(last(&a), Some("m = 2 → ()".to_owned()));
+    assert_eq!(last(&a), Some("m → 3".to_owned()));
 
     // trailing comma:
     let i = infov!(4,);
     infov!("trailing comma", i,);
     logv!(target: "special", Level::Info, i,);
+    assert_eq!(last(&a), Some("i → 4".to_owned()));
+
+    // Lazy -l macros, only evaluating the expression if enabled
+    let mut calls = 0;
+    let mut summary = |v: &[u32]| { calls += 1; v.iter().sum::<u32>() };
+    let v = vec![1, 2, 3];
+    assert_eq!(tracel!(summary(&v)), None);
+    assert_eq!(logl!(Level::Trace, "state", summary(&v)), None);
+    assert_eq!(last(&a), None);
+    assert_eq!(debugl!("state", summary(&v)), Some(6));
+    assert_eq!(last(&a), Some("state summary(&v) → 6".to_owned()));
+    assert_eq!(infol!(target: "special", "sum", "{:x}", v.len() * 4, summary(&v)),
+               Some((12, 6)));
+    assert_eq!(last(&a), Some("sum v.len() * 4 → c, summary(&v) → 6".to_owned()));
+    assert_eq!(logl!(target: "special", Level::Warn, v.len()), Some(3));
+    assert_eq!(last(&a), Some("v.len() → 3".to_owned()));
+    assert_eq!(warnl!(v[0]), Some(1));
+    assert_eq!(last(&a), Some("v[0] → 1".to_owned()));
+    assert_eq!(errorl!(target: "special", v[1]), Some(2));
+    assert_eq!(last(&a), Some("v[1] → 2".to_owned()));
+    assert_eq!(calls, 2);
+
+    // Result, with Ok and Err at different levels, returned unchanged
+    #[derive(Debug)]
+    struct Failed(Option<std::num::ParseIntError>);
+    impl std::fmt::Display for Failed {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("failed")
+        }
+    }
+    impl std::error::Error for Failed {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.0.as_ref().map(|e| e as &(dyn std::error::Error + 'static))
+        }
+    }
+    fn parse(s: &str) -> Result<i32, Failed> {
+        s.parse().map_err(|e| Failed(Some(e)))
+    }
+    assert_eq!(debugv_result!(parse("3")).unwrap(), 3);
+    assert_eq!(last(&a), Some("parse(\"3\") → Ok(3)".to_owned()));
+    assert!(tracev!(parse("x")).is_err());
+    assert_eq!(last(&a), None);
+    assert!(tracev_result!(parse("x")).is_err());
+    assert_eq!(
+        last(&a),
+        Some("parse(\"x\") → Err(Failed(Some(ParseIntError { kind: InvalidDigit })))"
+             .to_owned())
+    );
+    let r: Result<(), Failed> = Err(Failed(None));
+    assert!(logv_result!(target: "special", Level::Info, err: Level::Warn,
+                         "try", r).is_err());
+    assert_eq!(last(&a), Some("try r → Err(Failed(None))".to_owned()));
+    let r: Result<(), Failed> = Err(Failed(None));
+    assert!(errorv_result!(target: "special", err: Level::Warn, r).is_err());
+    assert_eq!(last(&a), Some("r → Err(Failed(None))".to_owned()));
+    let r: Result<u8, Failed> = Ok(1);
+    assert_eq!(errorv_result!(r).unwrap(), 1);
+    assert_eq!(last(&a), Some("r → Ok(1)".to_owned()));
+
+    // With the error's source chain, also via `Box<dyn Error>`
+    let j = 33;
+    assert_eq!(infov!(infov_result!(sources, parse("4")).unwrap() + j), 37);
+    assert_eq!(
+        last(&a),
+        Some("infov_result!(sources, parse(\"4\")).unwrap() + j → 37".to_owned())
+    );
+    fn boxed() -> Result<(), Box<dyn std::error::Error>> {
+        parse("?")?;
+        Ok(())
+    }
+    assert!(warnv_result!(sources, "boxed", boxed()).is_err());
+    assert_eq!(
+        last(&a),
+        Some("boxed boxed() → Err(Failed(Some(ParseIntError { kind: InvalidDigit }))): \
+              invalid digit found in string".to_owned())
+    );
+
+    // tryv, logging only a failure, then returning early like `?`
+    fn try_sum(a: &str, b: &str) -> Result<i32, Box<dyn std::error::Error>> {
+        let a = tryv!(parse(a));
+        let b = tryv!(target: "special", err: Level::Warn, "second", parse(b));
+        Ok(a + b)
+    }
+    assert_eq!(try_sum("1", "2").unwrap(), 3);
+    assert_eq!(last(&a), None);
+    assert!(try_sum("x", "2").is_err());
+    assert_eq!(
+        last(&a),
+        Some("parse(a) → Err(Failed(Some(ParseIntError { kind: InvalidDigit })))"
+             .to_owned())
+    );
+    assert!(try_sum("1", "y").is_err());
+    assert_eq!(
+        last(&a),
+        Some("second parse(b) → Err(Failed(Some(ParseIntError { kind: InvalidDigit })))"
+             .to_owned())
+    );
+
+    // tryv of Option, success type not requiring `Debug`
+    struct Opaque(u8);
+    fn try_first(v: &[Opaque]) -> Option<u8> {
+        Some(tryv!(v.first()).0)
+    }
+    assert_eq!(try_first(&[Opaque(7)]), Some(7));
+    assert_eq!(last(&a), None);
+    assert_eq!(try_first(&[]), None);
+    assert_eq!(last(&a), Some("v.first() → None".to_owned()));
+
+    // Error source chain, on one line or multi-line, returning the error
+    let e = parse("x").unwrap_err();
+    let e = error_chain!(target: "special", "while parsing", e);
+    assert_eq!(
+        last(&a),
+        Some("while parsing: failed: invalid digit found in string".to_owned())
+    );
+    warn_chain!(multiline, &e);
+    assert_eq!(
+        last(&a),
+        Some("failed\n  caused by: invalid digit found in string".to_owned())
+    );
+    let be = boxed().unwrap_err();
+    log_chain!(Level::Info, "boxed", &be);
+    assert_eq!(
+        last(&a),
+        Some("boxed: failed: invalid digit found in string".to_owned())
+    );
+    debug_chain!(Failed(None));
+    assert_eq!(last(&a), Some("failed".to_owned()));
+    trace_chain!(multiline, backtrace, "trace", &e);
+    assert_eq!(last(&a), None);
+    info_chain!(backtrace, &e);
+    let m = last(&a).unwrap();
+    assert!(m.starts_with("failed: invalid digit found in string"), "{}", m);
+    assert!(!m.contains('\n') || m.contains("\nlog site backtrace:\n"), "{}", m);
+
+    // logv_time, with elapsed time (checked by prefix and suffix only)
+    fn work(n: u32) -> u32 {
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        n * 2
+    }
+    fn timed(msg: Option<String>, start: &str) {
+        let msg = msg.unwrap();
+        assert!(msg.starts_with(start), "{}", msg);
+        assert!(msg.ends_with("ms)"), "{}", msg);
+    }
+    assert_eq!(debugv_time!(work(3)), 6);
+    timed(last(&a), "work(3) → 6 (in ");
+    assert_eq!(logv_time!(target: "special", Level::Info, "timed", "{:#x}", work(8)), 16);
+    timed(last(&a), "timed work(8) → 0x10 (in ");
+    debugv_time!(novalue, "opaque", Opaque(work(1) as u8));
+    timed(last(&a), "opaque Opaque(work(1) as u8) (in ");
+    tracev_time!(work(1));
+    assert_eq!(last(&a), None);
+    assert_eq!(errorv_time!(target: "special", work(4)), 8);
+    timed(last(&a), "work(4) → 8 (in ");
+
+    // slowv, logging only over the threshold
+    let limit = std::time::Duration::from_millis(1);
+    assert_eq!(slowv!(std::time::Duration::from_secs(60), work(2)), 4);
+    assert_eq!(last(&a), None);
+    assert_eq!(slowv!(limit, work(2)), 4);
+    let msg = last(&a).unwrap();
+    assert!(msg.starts_with("work(2) (in "), "{}", msg);
+    assert!(msg.ends_with("ms > 1ms)"), "{}", msg);
+    slowv!(target: "special", level: Level::Info, limit, "slow", Opaque(work(1) as u8));
+    timed(last(&a), "slow Opaque(work(1) as u8) (in ");
+    slowv!(level: Level::Trace, limit, work(1));
+    assert_eq!(last(&a), None);
+
+    // scopev, logging entry and exit, indented when nested
+    fn exited(msg: Option<String>, start: &str) {
+        let msg = msg.unwrap();
+        assert!(msg.starts_with(start), "{}", msg);
+        assert!(msg.ends_with(")"), "{}", msg);
+    }
+    {
+        let _outer = scopev!("outer");
+        assert_eq!(last(&a), Some("→ outer".to_owned()));
+        {
+            let _inner = scopev!(target: "special", Level::Info, "inner");
+            assert_eq!(last(&a), Some("  → inner".to_owned()));
+            let _quiet = scopev!(Level::Trace, "quiet");
+            assert_eq!(last(&a), None);
+        }
+        exited(last(&a), "  ← inner (in ");
+    }
+    exited(last(&a), "← outer (in ");
+
+    // scopev, flagged on exit when unwinding from a panic (quietly)
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let res = std::panic::catch_unwind(|| {
+        let _s = scopev!("panicky");
+        panic!("expected test panic");
+    });
+    std::panic::set_hook(hook);
+    assert!(res.is_err());
+    let msg = last(&a).unwrap();
+    assert!(msg.starts_with("← panicky (in "), "{}", msg);
+    assert!(msg.ends_with(", panicked)"), "{}", msg);
+
+    // Once per callsite, formatted and -v forms
+    for i in 0..3 {
+        warn_once!("once {}", i);
+        if i == 0 {
+            assert_eq!(last(&a), Some("once 0".to_owned()));
+        } else {
+            assert_eq!(last(&a), None);
+        }
+    }
+    let mut sum = 0;
+    for i in 0..3 {
+        assert_eq!(logv_once!(target: "special", Level::Info, -i), -i);
+        assert_eq!(tracev_once!(i), i);
+        let (x, y) = debugv_once!("first", "{:x}", i, -i);
+        sum += x - y;
+        if i == 0 {
+            assert_eq!(last(&a), Some("first i → 0, -i → 0".to_owned()));
+        } else {
+            assert_eq!(last(&a), None);
+        }
+    }
+    assert_eq!(sum, 6);
+    assert_eq!(last(&a), None);
+
+    // Trace not enabled, so the once is still available
+    trace_once!("not yet");
+    log::set_max_level(LevelFilter::Trace);
+    for _ in 0..2 {
+        trace_once!("now");
+    }
+    log::set_max_level(LevelFilter::Debug);
+    assert_eq!(last(&a), Some("now".to_owned()));
+
+    // Every nth per callsite, with suppressed count
+    let mut logged = Vec::new();
+    for i in 0..7 {
+        info_every_n!(3, target: "special", "hit {}", i);
+        logged.extend(last(&a));
+    }
+    assert_eq!(logged, vec![
+        "hit 0".to_owned(),
+        "hit 3 (suppressed 2 similar messages)".to_owned(),
+        "hit 6 (suppressed 2 similar messages)".to_owned(),
+    ]);
+    let mut logged = Vec::new();
+    for i in 0..4 {
+        assert_eq!(errorv_every_n!(5, "e", i, i), (i, i));
+        logged.extend(last(&a));
+        assert_eq!(logv_every_n!(2, Level::Warn, i), i);
+        logged.extend(last(&a));
+    }
+    assert_eq!(logged, vec![
+        "e i → 0, i → 0".to_owned(),
+        "i → 0".to_owned(),
+        "i → 2 (suppressed 1 similar messages)".to_owned(),
+    ]);
+    // An `n` of 0 is taken as 1
+    let mut logged = Vec::new();
+    for i in 0..2 {
+        assert_eq!(infov_every_n!(0, i), i);
+        logged.extend(last(&a));
+    }
+    assert_eq!(logged, vec!["i → 0".to_owned(), "i → 1".to_owned()]);
+
+    // Throttled per callsite, with suppressed count on the next record
+    let iv = std::time::Duration::from_millis(50);
+    let mut logged = Vec::new();
+    for i in 0..4 {
+        if i == 3 {
+            std::thread::sleep(std::time::Duration::from_millis(60));
+        }
+        warn_throttled!(iv, target: "special", "throttled {}", i);
+        logged.extend(last(&a));
+    }
+    assert_eq!(logged, vec![
+        "throttled 0".to_owned(),
+        "throttled 3 (suppressed 2 similar messages)".to_owned(),
+    ]);
+    let mut logged = Vec::new();
+    let mut sum = 0;
+    for i in 0..4 {
+        if i == 3 {
+            std::thread::sleep(std::time::Duration::from_millis(60));
+        }
+        sum += debugv_throttled!(iv, "v", i);
+        logged.extend(last(&a));
+        assert_eq!(tracev_throttled!(iv, i), i);
+        assert_eq!(last(&a), None);
+    }
+    assert_eq!(sum, 6);
+    assert_eq!(logged, vec![
+        "v i → 0".to_owned(),
+        "v i → 3 (suppressed 2 similar messages)".to_owned(),
+    ]);
+
+    // -v_check, explaining a condition via its evaluated sub-expressions
+    #[cfg(feature = "proc-macro")] {
+        let (v, limit, flag) = (vec![1, 2, 3], 2, true);
+        if debugv_check!(v.len() > limit && flag) {
+            assert_eq!(
+                last(&a),
+                Some("v.len() > limit && flag → true\n  \
+                      v.len() > limit → true\n    \
+                      v.len() → 3\n    \
+                      limit → 2\n  \
+                      flag → true".to_owned())
+            );
+        } else {
+            unreachable!();
+        }
+
+        // Short circuit, with only evaluated operands logged
+        let mut calls = 0;
+        let mut call = |b: bool| { calls += 1; b };
+        assert!(!infov_check!(target: "special", "gate", v.is_empty() || call(false)
+                              || (limit > 2 && call(true))));
+        assert_eq!(calls, 1);
+        assert_eq!(
+            last(&a),
+            Some("gate v.is_empty() || call(false) || (limit > 2 && call(true)) → false\n  \
+                  v.is_empty() → false\n  \
+                  call(false) → false\n  \
+                  limit > 2 && call(true) → false\n    \
+                  limit > 2 → false\n      \
+                  limit → 2".to_owned())
+        );
+
+        // Explicit level, a leaf condition, and disabled levels still evaluate
+        assert!(logv_check!(Level::Warn, flag));
+        assert_eq!(last(&a), Some("flag → true".to_owned()));
+        assert!(!logv_check!(target: "special", Level::Info, "t", !flag));
+        assert_eq!(last(&a), Some("t !flag → false".to_owned()));
+        assert!(!errorv_check!(v.len() < 3));
+        assert_eq!(last(&a), Some("v.len() < 3 → false\n  v.len() → 3".to_owned()));
+        assert!(warnv_check!(v.len() == 3));
+        assert_eq!(last(&a), Some("v.len() == 3 → true\n  v.len() → 3".to_owned()));
+        assert!(debugv_check!("d", flag));
+        assert_eq!(last(&a), Some("d flag → true".to_owned()));
+        assert!(debugv_check!(!v.is_empty() && v[0] == 1));
+        assert_eq!(
+            last(&a),
+            Some("!v.is_empty() && v[0] == 1 → true\n  \
+                  !v.is_empty() → true\n  \
+                  v[0] == 1 → true\n    \
+                  v[0] → 1".to_owned())
+        );
+        let mut calls = 0;
+        assert!(tracev_check!({ calls += 1; flag }));
+        assert_eq!(calls, 1);
+        assert_eq!(last(&a), None);
+        assert!(logv_check!(Level::Trace, "p", flag));
+        assert_eq!(last(&a), None);
+    }
 
     // ### compiler accepted mis-features ###
     //
     // These are pretty innocuous, just resulting in unexpected output, but
-    // worth documentating. Proc-macros could produce sane compiler errors for
-    // these in the future.
-
-    // Extra space if empty prefix is given: No great way to check that at
-    // compile time.
-    debugv!("", 22);
-    assert_eq!(last(&a), Some(" 22 → 22".to_owned()));
-
-    // Non-string prefix literal is allowed
-    warnv!(666, 1);
-    assert_eq!(last(&a), Some("666 1 → 1".to_owned()));
-
-    // 2 placeholders in aggregate, wrong params
-    let i = 4;
-    debugv!("prefix with {:?}", "without", i);
-    assert_eq!(last(&a), Some("prefix with \"i\" 4 → without".to_owned()));
+    // worth documentating. With the proc-macro feature, these are instead
+    // compile errors (see ./test_compile_errors), or handled as expected.
+    #[cfg(not(feature = "proc-macro"))] {
+        // Extra space if empty prefix is given: No great way to check that at
+        // compile time.
+        debugv!("", 22);
+        assert_eq!(last(&a), Some(" 22 → 22".to_owned()));
+
+        // Non-string prefix literal is allowed
+        warnv!(666, 1);
+        assert_eq!(last(&a), Some("666 1 → 1".to_owned()));
+
+        // 2 placeholders in aggregate, wrong params. With the kv feature, the
+        // value format is also applied separately, and this fails to compile.
+        #[cfg(not(feature = "kv"))] {
+            let i = 4;
+            debugv!("prefix with {:?}", "without", i);
+            assert_eq!(last(&a), Some("prefix with \"i\" 4 → without".to_owned()));
+        }
+    }
+
+    #[cfg(feature = "proc-macro")] {
+        // Empty prefix is the same as no prefix
+        debugv!("", 22);
+        assert_eq!(last(&a), Some("22 → 22".to_owned()));
+        debugv!("", "{}", 22, 23);
+        assert_eq!(last(&a), Some("22 → 22, 23 → 23".to_owned()));
+
+        // Escaped braces in prefix and value format
+        debugv!("{{prefix}}", "{{{}}}", 22);
+        assert_eq!(last(&a), Some("{prefix} 22 → {22}".to_owned()));
+
+        // Custom templates, with the expression and value slots repeated
+        // for multiple expressions
+        let (i, j) = (32, 19);
+        assert_eq!(debugv!(template = "{expr} = {value}", i), 32);
+        assert_eq!(last(&a), Some("i = 32".to_owned()));
+        debugv!(template = "{prefix}: {expr}={value}", "ij", "{:x}", i, j);
+        assert_eq!(last(&a), Some("ij: i=20, j=13".to_owned()));
+        let ctx = "req";
+        warnv!(target: "special", template = "[{value}] {prefix}", prefix = ctx, i);
+        assert_eq!(last(&a), Some("[32] req".to_owned()));
+        debugv!(template = "{{{value}}}{prefix}", i, j);
+        assert_eq!(last(&a), Some("{32, 19}".to_owned()));
+        assert_eq!(debugv_once!(template = "{expr}: {value}", i), 32);
+        assert_eq!(last(&a), Some("i: 32".to_owned()));
+        assert_eq!(tracel!(template = "{expr}: {value}", i), None);
+    }
+
+    // Key-value pairs of expression and value, and any prefix
+    #[cfg(feature = "kv")] {
+        let i = 32;
+        debugv!(i);
+        assert_eq!(last_kvs(&a), kvs(&[("i", "32")]));
+        assert_eq!(last(&a), Some("i → 32".to_owned()));
+
+        let vt = "foo";
+        infov!("index", "{:#x}", i);
+        assert_eq!(last_kvs(&a), kvs(&[("prefix", "index"), ("i", "0x20")]));
+        infov!("index", "{}", vt);
+        assert_eq!(last_kvs(&a), kvs(&[("prefix", "index"), ("vt", "foo")]));
+        infov!("{{index}}", i);
+        assert_eq!(last_kvs(&a), kvs(&[("prefix", "{index}"), ("i", "32")]));
+        assert_eq!(last(&a), Some("{index} i → 32".to_owned()));
+        infov!(prefix = vt, i);
+        assert_eq!(last_kvs(&a), kvs(&[("prefix", "foo"), ("i", "32")]));
+        infov!("named", "{:x}", n = i, j = 19);
+        assert_eq!(
+            last_kvs(&a),
+            kvs(&[("prefix", "named"), ("n", "20"), ("j", "13")])
+        );
+        infov!(target: "special", "pair", i, vt);
+        assert_eq!(
+            last_kvs(&a),
+            kvs(&[("prefix", "pair"), ("i", "32"), ("vt", "\"foo\"")])
+        );
+
+        let r: Result<u8, std::fmt::Error> = Err(std::fmt::Error);
+        debugv_result!(sources, "res", r).ok();
+        assert_eq!(
+            last_kvs(&a),
+            kvs(&[("prefix", "res"), ("r", "Err(Error)"), ("sources", "")])
+        );
+        last(&a);
+
+        let e = parse("x").unwrap_err();
+        error_chain!("chain", &e);
+        assert_eq!(
+            last_kvs(&a),
+            kvs(&[("prefix", "chain"), ("error", "failed"),
+                  ("sources", ": invalid digit found in string")])
+        );
+        last(&a);
+
+        #[cfg(feature = "proc-macro")] {
+            debugv_check!("check", i > 30);
+            assert_eq!(
+                last_kvs(&a),
+                kvs(&[("prefix", "check"), ("i > 30", "true")])
+            );
+            last(&a);
+        }
+
+        fn try_kv() -> Option<u8> {
+            Some(tryv!("kv", None::<u8>))
+        }
+        try_kv();
+        assert_eq!(last_kvs(&a), kvs(&[("prefix", "kv"), ("None::<u8>", "None")]));
+        last(&a);
+
+        for _ in 0..3 {
+            infov_every_n!(2, prefix = vt, i);
+        }
+        assert_eq!(
+            last_kvs(&a),
+            kvs(&[("prefix", "foo"), ("i", "32"), ("suppressed", "1")])
+        );
+        assert_eq!(
+            last(&a),
+            Some("foo i → 32 (suppressed 1 similar messages)".to_owned())
+        );
+
+        let v = infov_time!("t", vt);
+        let kv = last_kvs(&a);
+        assert_eq!(&kv[..2], &kvs(&[("prefix", "t"), ("vt", "\"foo\"")])[..]);
+        assert_eq!(kv[2].0, "elapsed");
+        assert_eq!(v, "foo");
+        last(&a);
+
+        // Non-kv logging is unaffected
+        info!("plain");
+        assert_eq!(last_kvs(&a), kvs(&[]));
+        assert_eq!(last(&a), Some("plain".to_owned()));
+
+        // Trace disabled, no log, no kv
+        tracev!(i);
+        assert_eq!(last_kvs(&a), kvs(&[]));
+        assert_eq!(last(&a), None);
+    }
 
     info!("End of test (passed)");
 }
@@ -0,0 +1,215 @@
+//! Stateful tests of the logfn attribute macro (proc-macro feature)
+
+use tao_log::*;
+
+use std::sync::{Arc, Mutex};
+use log::{Level, LevelFilter, Log, Record, Metadata};
+
+#[cfg(feature = "std")]
+use log::set_boxed_logger;
+
+#[cfg(not(feature = "std"))]
+fn set_boxed_logger(logger: Box<dyn Log>) -> Result<(), log::SetLoggerError> {
+    log::set_logger(Box::leak(logger))
+}
+
+struct State {
+    logs: Mutex<Vec<(Level, String, String)>>,
+}
+
+// Take all logs as (level, target, message) since last call.
+fn take(state: &State) -> Vec<(Level, String, String)> {
+    std::mem::replace(&mut *state.logs.lock().unwrap(), Vec::new())
+}
+
+fn logs(expected: &[(Level, &str, &str)]) -> Vec<(Level, String, String)> {
+    expected.iter()
+        .map(|&(l, t, m)| (l, t.to_owned(), m.to_owned()))
+        .collect()
+}
+
+struct Logger(Arc<State>);
+
+impl Log for Logger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let msg = format!("{}", record.args());
+
+        // With the ascii-arrow feature, check that no arrows remain, then
+        // restore them, for the same assertions.
+        #[cfg(feature = "ascii-arrow")]
+        let msg = {
+            assert!(!msg.contains('→'), "{}", msg);
+            msg.replace("->", "→")
+        };
+        assert_eq!(record.file(), Some(file!()));
+        self.0.logs.lock().unwrap().push(
+            (record.level(), record.target().to_owned(), msg)
+        );
+        println!("{:5} {}", record.level(), record.args());
+    }
+
+    fn flush(&self) {}
+}
+
+#[logfn]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[logfn(level = "info", target = "special", display(name), skip(_unused))]
+fn greet(name: &str, count: usize, _unused: ()) -> String {
+    format!("hello {} x{}", name, count)
+}
+
+#[logfn(level = "trace")]
+fn quiet(a: i32) -> i32 {
+    a
+}
+
+#[logfn]
+fn nothing() {}
+
+#[logfn]
+fn parse(s: &str) -> Result<i64, std::num::ParseIntError> {
+    if s.is_empty() {
+        return Ok(0);
+    }
+    let v = s.parse::<i64>()?;
+    Ok(v)
+}
+
+type Res = Result<u8, &'static str>;
+
+#[logfn(level = "info", err = "warn")]
+fn checked(v: u8) -> Res {
+    if v > 9 { Err("too big") } else { Ok(v) }
+}
+
+#[logfn(ret(skip))]
+fn evens(max: u32) -> impl Iterator<Item = u32> {
+    (0..max).filter(|v| v % 2 == 0)
+}
+
+#[logfn]
+fn generic<T: std::fmt::Debug + Clone>(v: &T) -> T {
+    v.clone()
+}
+
+// The crate, as if re-exported by another crate
+mod reexport {
+    pub use tao_log as tl;
+}
+
+#[logfn(crate = "crate::reexport::tl", level = "info")]
+fn via_reexport(a: i32) -> i32 {
+    a
+}
+
+#[derive(Debug)]
+struct Counter(u32);
+
+impl Counter {
+    #[logfn]
+    fn incr(&mut self, by: u32) -> u32 {
+        self.0 += by;
+        self.0
+    }
+}
+
+fn main() {
+    let me = Arc::new(State { logs: Mutex::new(Vec::new()) });
+    let a = me.clone();
+    set_boxed_logger(Box::new(Logger(me))).unwrap();
+
+    log::set_max_level(LevelFilter::Debug);
+
+    info!("Start of test (test output follows)");
+    take(&a);
+
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(take(&a), logs(&[
+        (Level::Debug, "logfn", "add(a → 1, b → 2)"),
+        (Level::Debug, "logfn", "add → 3"),
+    ]));
+
+    assert_eq!(greet("world", 2, ()), "hello world x2");
+    assert_eq!(take(&a), logs(&[
+        (Level::Info, "special", "greet(name → world, count → 2)"),
+        (Level::Info, "special", "greet → \"hello world x2\""),
+    ]));
+
+    // Disabled level, still returns
+    assert_eq!(quiet(5), 5);
+    assert_eq!(take(&a), logs(&[]));
+
+    nothing();
+    assert_eq!(take(&a), logs(&[
+        (Level::Debug, "logfn", "nothing()"),
+        (Level::Debug, "logfn", "nothing → ()"),
+    ]));
+
+    // Result, with early return and `?`, Err at error level by default
+    assert_eq!(parse(""), Ok(0));
+    assert_eq!(take(&a), logs(&[
+        (Level::Debug, "logfn", "parse(s → \"\")"),
+        (Level::Debug, "logfn", "parse → Ok(0)"),
+    ]));
+    assert!(parse("x").is_err());
+    assert_eq!(take(&a), logs(&[
+        (Level::Debug, "logfn", "parse(s → \"x\")"),
+        (Level::Error, "logfn",
+         "parse → Err(ParseIntError { kind: InvalidDigit })"),
+    ]));
+
+    // Result alias, with explicit err level
+    assert_eq!(checked(3), Ok(3));
+    assert_eq!(take(&a)[1], (Level::Info, "logfn".to_owned(), "checked → Ok(3)".to_owned()));
+    assert_eq!(checked(10), Err("too big"));
+    assert_eq!(
+        take(&a)[1],
+        (Level::Warn, "logfn".to_owned(), "checked → Err(\"too big\")".to_owned())
+    );
+
+    // `impl Trait` return (not Debug) is logged as is
+    #[derive(Debug)]
+    struct Opaque;
+    #[logfn(skip(o))]
+    fn opaque(o: Opaque) -> impl std::fmt::Debug {
+        o
+    }
+    opaque(Opaque);
+    assert_eq!(take(&a)[1].2, "opaque → Opaque");
+    assert_eq!(evens(5).count(), 3);
+    assert_eq!(take(&a), logs(&[(Level::Debug, "logfn", "evens(max → 5)")]));
+
+    #[logfn(ret(display))]
+    fn name() -> &'static str {
+        "bob"
+    }
+    name();
+    assert_eq!(take(&a)[1].2, "name → bob");
+
+    assert_eq!(generic(&"s"), "s");
+    assert_eq!(take(&a)[0].2, "generic(v → \"s\")");
+
+    // Methods, receiver not logged
+    let mut c = Counter(1);
+    assert_eq!(c.incr(2), 3);
+    assert_eq!(take(&a), logs(&[
+        (Level::Debug, "logfn", "incr(by → 2)"),
+        (Level::Debug, "logfn", "incr → 3"),
+    ]));
+
+    // Crate path option
+    assert_eq!(via_reexport(4), 4);
+    assert_eq!(take(&a), logs(&[
+        (Level::Info, "logfn", "via_reexport(a → 4)"),
+        (Level::Info, "logfn", "via_reexport → 4"),
+    ]));
+
+    info!("End of test (passed)");
+}
@@ -0,0 +1,95 @@
+//! Tests of the panic hook (testing feature)
+
+use std::env;
+use std::panic;
+use std::process::Command;
+use std::sync::Once;
+use std::thread;
+
+use tao_log::*;
+use tao_log::testing::{self, Captured};
+
+fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        tao_log::panic_hook::Hook::new()
+            .target("crash")
+            .backtrace(true)
+            .install();
+    });
+}
+
+fn errors(recs: Vec<Captured>) -> Vec<Captured> {
+    recs.into_iter().filter(|r| r.level == log::Level::Error).collect()
+}
+
+#[test]
+fn logs_panic() {
+    install();
+    let cap = testing::capture();
+    let line = line!() + 1;
+    let res = panic::catch_unwind(|| panic!("boom {}", 1));
+    assert!(res.is_err());
+    let recs = errors(cap.records());
+    assert_eq!(recs.len(), 1);
+    let r = &recs[0];
+    assert_eq!(r.target, "crash");
+    assert_eq!(r.file.as_ref().map(String::as_str), Some(file!()));
+    assert_eq!(r.line, Some(line));
+    let name = thread::current().name().unwrap().to_owned();
+    let expected = format!(
+        "thread '{}' panicked at 'boom 1', {}:{}:", name, file!(), line
+    );
+    assert!(r.msg.starts_with(&expected), "{}", r.msg);
+    assert!(r.msg.lines().count() == 1 || r.msg.contains("\nstack backtrace:\n"),
+            "{}", r.msg);
+}
+
+#[test]
+fn logs_named_thread() {
+    install();
+    thread::Builder::new().name("worker".to_owned()).spawn(|| {
+        let cap = testing::capture();
+        let res = panic::catch_unwind(|| {
+            "x".parse::<u8>().ok().unwrap()
+        });
+        assert!(res.is_err());
+        let recs = errors(cap.records());
+        assert_eq!(recs.len(), 1);
+        assert!(recs[0].msg.starts_with(
+            "thread 'worker' panicked at 'called `Option::unwrap()` on a `None` value'"
+        ), "{}", recs[0].msg);
+    }).unwrap().join().unwrap();
+}
+
+#[test]
+fn fatal_logged_once() {
+    install();
+    let cap = testing::capture();
+    let res = panic::catch_unwind(|| fatal!("fatal msg"));
+    assert!(res.is_err());
+    let recs = errors(cap.records());
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].msg, "fatal msg");
+}
+
+// With the maximum level off, as set globally, in a child process of this
+// test alone.
+#[test]
+fn level_off_not_logged() {
+    if env::var_os("TAO_LOG_PANIC_CHILD").is_some() {
+        install();
+        let cap = testing::capture();
+        log::set_max_level(log::LevelFilter::Off);
+        let res = panic::catch_unwind(|| panic!("quiet"));
+        assert!(res.is_err());
+        assert!(cap.records().is_empty());
+        return;
+    }
+    let out = Command::new(env::current_exe().unwrap())
+        .args(&["--exact", "level_off_not_logged", "--test-threads", "1"])
+        .env("TAO_LOG_PANIC_CHILD", "1")
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stdout));
+}
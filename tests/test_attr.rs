@@ -0,0 +1,100 @@
+//! Tests of the `#[testing::test]` attribute (proc-macro and testing
+//! features).
+
+use tao_log::*;
+use tao_log::testing::{self, Capture};
+
+#[testing::test]
+fn no_argument() {
+    info!("logged");
+    assert_logged!(level = Info, msg = "logged");
+}
+
+#[testing::test]
+fn capture_argument(cap: &Capture) {
+    debugv!(1 + 1);
+    let expected = if cfg!(feature = "ascii-arrow") {
+        "1 + 1 -> 2"
+    } else {
+        "1 + 1 → 2"
+    };
+    assert_eq!(cap.last().unwrap().msg, expected);
+}
+
+#[testing::test]
+fn result_return() -> Result<(), std::num::ParseIntError> {
+    let v: i32 = tryv!("3".parse());
+    assert_eq!(v, 3);
+    assert_not_logged!(level = Error);
+    Ok(())
+}
+
+// The crate, as if re-exported by another crate
+mod reexport {
+    pub use tao_log as tl;
+}
+
+#[testing::test(crate = "crate::reexport::tl")]
+fn crate_path() {
+    info!("via re-export");
+    assert_logged!(msg = "via re-export");
+}
+
+#[testing::test(check = "static_msg_logged")]
+#[should_panic]
+fn fatal_static_msg() {
+    fatal!("static fatal msg");
+}
+
+fn static_msg_logged(_cap: &Capture) {
+    assert_logged!(level = Error, msg = "static fatal msg");
+}
+
+#[testing::test(check = "format_msg_logged")]
+#[should_panic(expected = "fmt fatal")]
+fn fatal_format_msg() {
+    fatal!(target: "grim", "fmt {} msg", "fatal".to_owned());
+}
+
+fn format_msg_logged(cap: &Capture) {
+    assert_logged!(level = Error, target = "grim", msg = "fmt fatal msg");
+    assert_eq!(cap.records().len(), 1);
+}
+
+// Failures of the check, or of the `should_panic` expectations, fail the
+// test. As attributed functions are themselves tests, these use the
+// underlying runtime functions directly.
+
+mod failing {
+    use tao_log::*;
+    use tao_log::testing::{Capture, __run, __run_should_panic};
+
+    fn check_fails(_cap: &Capture) {
+        assert_logged!(msg = "never");
+    }
+
+    #[test]
+    #[should_panic(expected = "assert_logged!(level = Error, msg = \"static fatal msg\") failed")]
+    fn other_msg() {
+        __run_should_panic(None, |_| fatal!("other fatal msg"),
+                           super::static_msg_logged);
+    }
+
+    #[test]
+    #[should_panic(expected = "captured records (1):\n    INFO  test_attr::failing: returned")]
+    fn check_after_return() {
+        __run(|_| info!("returned"), check_fails);
+    }
+
+    #[test]
+    #[should_panic(expected = "test did not panic as expected")]
+    fn no_panic() {
+        __run_should_panic(None, |_| {}, |_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "panic did not contain expected string")]
+    fn wrong_panic() {
+        __run_should_panic(Some("other"), |_| panic!("unexpected"), |_| {});
+    }
+}
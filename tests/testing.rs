@@ -0,0 +1,164 @@
+//! Tests of the testing module capture logger (testing feature), run in
+//! parallel.
+
+use std::thread;
+
+use log::{Level, LevelFilter};
+use tao_log::*;
+use tao_log::testing::{capture, capture_level};
+
+// Return the expected message with the arrows of the ascii-arrow feature,
+// if enabled.
+fn arrows(msg: &str) -> String {
+    if cfg!(feature = "ascii-arrow") {
+        msg.replace('→', "->")
+    } else {
+        msg.to_owned()
+    }
+}
+
+#[test]
+fn captures_fields() {
+    let cap = capture();
+    let i = 32;
+    debugv!(target: "special", "prefix", i);
+    let line = line!() - 1;
+    let rec = cap.last().unwrap();
+    assert_eq!(rec.level, Level::Debug);
+    assert_eq!(rec.target, "special");
+    assert_eq!(rec.msg, arrows("prefix i → 32"));
+    assert_eq!(rec.module_path.as_ref().map(String::as_str), Some("testing"));
+    assert_eq!(rec.file.as_ref().map(String::as_str), Some(file!()));
+    assert_eq!(rec.line, Some(line));
+    #[cfg(feature = "kv")]
+    assert_eq!(rec.kvs, vec![
+        ("prefix".to_owned(), "prefix".to_owned()),
+        ("i".to_owned(), "32".to_owned()),
+    ]);
+    #[cfg(not(feature = "kv"))]
+    assert!(rec.kvs.is_empty());
+    assert_eq!(cap.last(), None);
+}
+
+#[test]
+fn records_take_clear() {
+    let cap = capture();
+    for i in 0..3 {
+        info!("rec {}", i);
+    }
+    trace!("trace");
+    assert_eq!(cap.records().len(), 4);
+    let recs = cap.take();
+    assert_eq!(recs.len(), 4);
+    assert_eq!(recs[0].msg, "rec 0");
+    assert_eq!(recs[3].level, Level::Trace);
+    assert!(cap.records().is_empty());
+    warn!("again");
+    cap.clear();
+    assert!(cap.records().is_empty());
+}
+
+#[test]
+fn per_thread() {
+    let cap = capture();
+    info!("main");
+    thread::spawn(|| {
+        info!("uncaptured");
+        let inner = capture();
+        info!("spawned");
+        assert_eq!(inner.last().unwrap().msg, "spawned");
+    }).join().unwrap();
+    let recs = cap.take();
+    assert_eq!(recs.len(), 1);
+    assert_eq!(recs[0].msg, "main");
+}
+
+#[test]
+fn nested_and_level() {
+    let outer = capture();
+    error!("outer 1");
+    {
+        let inner = capture_level(LevelFilter::Info);
+        info!("inner");
+        debug!("filtered");
+        assert!(!log_enabled!(Level::Debug));
+        assert!(log_enabled!(Level::Info));
+        assert_eq!(inner.take().len(), 1);
+    }
+    error!("outer 2");
+    let recs = outer.take();
+    assert_eq!(recs.len(), 2);
+    assert_eq!(recs[1].to_string(), "ERROR testing: outer 2");
+}
+
+#[test]
+fn no_capture() {
+    info!("discarded");
+    assert!(!log_enabled!(Level::Error));
+}
+
+#[test]
+fn assertions() {
+    let _cap = capture();
+    let i = 32;
+    debugv!(target: "special", i);
+    info!("connection timeout after {}s", 5);
+    warn!("retrying");
+    assert_logged!(level = Debug, target = "special", msg = arrows("i → 32"));
+    assert_logged!(level = Level::Info, target = "testing");
+    let lvl = Level::Warn;
+    assert_logged!(level = lvl, msg = "retrying");
+    assert_not_logged!(level = if i > 0 { Level::Error } else { lvl });
+    assert_logged!(msg = "retrying",);
+    assert_not_logged!(level = Error);
+    assert_not_logged!(level = Debug, msg = arrows("i → 33"));
+    assert_logged_matches!(msg = "*timeout*");
+    assert_logged_matches!(target = "spec?al", msg = arrows("i → *"));
+    assert_logged_matches!(msg = "*");
+    assert_logged_in_order!((level = Debug), (msg = "retrying"));
+    assert_logged_in_order!(
+        (msg = arrows("i → 32")),
+        (level = Info, msg = "connection timeout after 5s"),
+        (level = Warn),
+    );
+}
+
+#[test]
+#[should_panic(expected = "assert_logged!(level = Info, msg = \"missing\") failed; \
+                           captured records (1):\n    INFO  testing: present")]
+fn assert_logged_fails() {
+    let _cap = capture();
+    info!("present");
+    assert_logged!(level = Info, msg = "missing");
+}
+
+#[test]
+#[should_panic(expected = "assert_not_logged!(msg = \"present\") failed")]
+fn assert_not_logged_fails() {
+    let _cap = capture();
+    info!("present");
+    assert_not_logged!(msg = "present");
+}
+
+#[test]
+#[should_panic(expected = "assert_logged_matches!(msg = \"pre?ent\") failed")]
+fn assert_logged_matches_fails() {
+    let _cap = capture();
+    info!("presents");
+    assert_logged_matches!(msg = "pre?ent");
+}
+
+#[test]
+#[should_panic(expected = "captured records (2)")]
+fn assert_logged_in_order_fails() {
+    let _cap = capture();
+    info!("first");
+    info!("second");
+    assert_logged_in_order!((msg = "second"), (msg = "first"));
+}
+
+#[test]
+#[should_panic(expected = "no capture on this thread")]
+fn assert_without_capture() {
+    assert_logged!(msg = "any");
+}